@@ -0,0 +1,58 @@
+// Linear scan vs HammingIndex for the store_symbol_at_coord merge decision
+// Run with: cargo bench --bench fingerprint_search
+
+use std::time::Instant;
+use version_004::{
+    FractalSymbol, HammingIndex, linear_first_within,
+    hash_to_mandelbrot_coord, julia_fingerprint_from_mandelbrot, mandelbrot_stability
+};
+
+const MERGE_THRESHOLD: u32 = 8;
+const SCALE: u32 = 256;
+
+fn main() {
+    for &symbol_count in &[1_000usize, 10_000, 50_000] {
+        // Build the symbol table the way store_symbol_at_coord does: only unmerged patterns are kept
+        let mut symbols: Vec<FractalSymbol> = Vec::new();
+        let mut index = HammingIndex::new(MERGE_THRESHOLD);
+        let mut i = 0;
+        while symbols.len() < symbol_count {
+            let coord = hash_to_mandelbrot_coord(&format!("symbol:{}", i));
+            let pattern = julia_fingerprint_from_mandelbrot(coord, SCALE);
+            if index.first_within(&symbols, &pattern, MERGE_THRESHOLD).is_none() {
+                index.insert(symbols.len(), &pattern);
+                symbols.push(FractalSymbol::new(coord, pattern, mandelbrot_stability(coord, SCALE)));
+            }
+            i += 1;
+        }
+
+        let queries: Vec<_> = (0..1_000)
+            .map(|i| julia_fingerprint_from_mandelbrot(hash_to_mandelbrot_coord(&format!("query:{}", i)), SCALE))
+            .collect();
+
+        let start = Instant::now();
+        let linear: Vec<Option<usize>> = queries.iter()
+            .map(|q| linear_first_within(&symbols, q, MERGE_THRESHOLD))
+            .collect();
+        let linear_time = start.elapsed();
+
+        let start = Instant::now();
+        let indexed: Vec<Option<usize>> = queries.iter()
+            .map(|q| index.first_within(&symbols, q, MERGE_THRESHOLD))
+            .collect();
+        let indexed_time = start.elapsed();
+
+        assert_eq!(linear, indexed, "index disagrees with linear scan");
+
+        let merges = indexed.iter().filter(|m| m.is_some()).count();
+        println!(
+            "{:>6} symbols: linear {:>10.2?}  indexed {:>10.2?}  ({:.1}x, {} merges / {} queries)",
+            symbol_count,
+            linear_time,
+            indexed_time,
+            linear_time.as_secs_f64() / indexed_time.as_secs_f64().max(1e-12),
+            merges,
+            queries.len()
+        );
+    }
+}
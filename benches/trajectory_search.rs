@@ -0,0 +1,75 @@
+// Linear scan vs SpatialIndex for the generate_response / generate_background_thought candidate search
+// Run with: cargo bench --bench trajectory_search
+
+use std::time::Instant;
+use version_004::{ConceptTrajectory, MindConfig, SpatialIndex, hash_to_mandelbrot_coord};
+
+// FractalMind::new's exploration_radius
+const EXPLORATION_RADIUS: f64 = 0.20;
+const POINTS_PER_TRAJECTORY: usize = 6;
+
+fn main() {
+    let config = MindConfig::default();
+    let searches = [
+        ("response", config.search_radius_factor),
+        ("thought", config.thought_radius_factor),
+    ];
+
+    for &trajectory_count in &[1_000usize, 10_000, 50_000] {
+        // Like text trajectories, every point is hashed on its own, so paths span the region
+        let trajectories: Vec<ConceptTrajectory> = (0..trajectory_count)
+            .map(|t| {
+                let path = (0..POINTS_PER_TRAJECTORY)
+                    .map(|i| hash_to_mandelbrot_coord(&format!("trajectory:{}:{}", t, i)))
+                    .collect();
+                ConceptTrajectory::new(path, String::new(), vec![0; POINTS_PER_TRAJECTORY])
+            })
+            .collect();
+
+        let mut index = SpatialIndex::new();
+        index.rebuild(&trajectories, &[]);
+
+        let queries: Vec<_> = (0..200)
+            .map(|i| hash_to_mandelbrot_coord(&format!("query:{}", i)))
+            .collect();
+
+        for &(search, factor) in &searches {
+            let radius = (EXPLORATION_RADIUS * factor).min(ConceptTrajectory::influence_radius());
+
+            // What the mind did before the index: closest_point on every trajectory
+            let start = Instant::now();
+            let linear: Vec<Vec<(usize, usize, f64)>> = queries.iter()
+                .map(|&q| {
+                    trajectories.iter().enumerate()
+                        .map(|(traj_idx, t)| {
+                            let (point_idx, dist) = t.closest_point(q);
+                            (traj_idx, point_idx, dist)
+                        })
+                        .filter(|&(_, _, dist)| dist <= radius)
+                        .collect()
+                })
+                .collect();
+            let linear_time = start.elapsed();
+
+            let start = Instant::now();
+            let indexed: Vec<Vec<(usize, usize, f64)>> = queries.iter()
+                .map(|&q| index.nearby_trajectories(&trajectories, q, radius))
+                .collect();
+            let indexed_time = start.elapsed();
+
+            assert_eq!(linear, indexed, "index disagrees with linear scan");
+
+            let found: usize = indexed.iter().map(Vec::len).sum();
+            println!(
+                "{:>6} trajectories, {:<8} r={:.2}: linear {:>10.2?}  indexed {:>10.2?}  ({:.1}x, {:.0} candidates per query)",
+                trajectory_count,
+                search,
+                radius,
+                linear_time,
+                indexed_time,
+                linear_time.as_secs_f64() / indexed_time.as_secs_f64().max(1e-12),
+                found as f64 / queries.len() as f64
+            );
+        }
+    }
+}
@@ -1,176 +1,215 @@
 // Audio encoding for fractal mind training
 // Converts audio streams into string representations that hash to Mandelbrot coordinates
 
-use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::Stream;
-use rustfft::{FftPlanner, num_complex::Complex};
-use std::sync::{Arc, Mutex};
+use std::path::Path;
+
+use crate::audio_source::{AudioSource, AudioFile, Microphone};
+use crate::spectral::{SpectralFrontEnd, WindowFunction, MelFilterbank, mfcc, power_to_db};
+use crate::pitch::{Note, yin, spectral_peaks};
+use crate::onset;
+use crate::sensory::SensoryVector;
+
+const MFCC_MEL_BANDS: usize = 26;
+const MEL_MIN_HZ: f32 = 20.0;
+const MIN_PITCH_CONFIDENCE: f32 = 0.8;
+/// Bins in the numeric form of encodings that have none of their own
+const VECTOR_FFT_BINS: usize = 32;
+/// Mel levels from this many dB below full scale up to 0dB map to 0..1 in the numeric form
+const VECTOR_DB_RANGE: f32 = 80.0;
 
 /// Encoding strategy for audio input
 #[derive(Clone, Copy)]
 pub enum AudioEncoding {
-    /// FFT frequency bins as comma-separated values (e.g., "0.2,0.8,0.3,0.1,...")
+    /// FFT magnitudes in log-spaced bins as comma-separated values (e.g., "fft:0.20,0.80,0.30,...")
     FrequencyBins { bin_count: usize },
 
-    /// Symbolic representation of frequency bands (e.g., "low:##,mid:--,high:.|")
+    /// Symbolic loudness of mel-spaced bands (e.g., "bands:@#|-..")
     BandSymbols { bands: usize },
 
-    /// Onset/beat detection events (e.g., "kick,snare,kick,hat")
+    /// Mel band energies in dB (e.g., "mel:-12,-20,-35,...")
+    MelBands { bands: usize },
+
+    /// Mel-frequency cepstral coefficients - timbre, mostly independent of loudness (e.g., "mfcc:-310,42,-8,...")
+    Mfcc { coefficients: usize },
+
+    /// Tempo rounded to 5 BPM, then each onset with the gap to the next one in sixteenth notes
+    /// (e.g., "onset:bpm120|kick:2,hat:2,snare:2,hat"). Gaps are left out when there is no tempo.
     OnsetEvents,
 
     /// Amplitude envelope over time (e.g., "amp:20,45,80,120,95,...")
     AmplitudeEnvelope { sample_rate_ms: usize },
 
-    /// Pitch estimation (e.g., "pitch:A440,pitch:C523")
-    PitchDetection,
+    /// YIN pitch as a note plus the strongest spectral peaks as notes (e.g., "pitch:A4+10c|peaks:A4,C#5,E5").
+    /// Cents are rounded to 10; "pitch:none" when no confident pitch is found between min_hz and max_hz.
+    PitchDetection { min_hz: f32, max_hz: f32, peaks: usize },
 }
 
-/// Captures audio and encodes it as strings
+/// Pulls windows from an AudioSource and encodes them as strings
 pub struct AudioEncoder {
     encoding: AudioEncoding,
-    sample_rate: u32,
-    buffer: Arc<Mutex<Vec<f32>>>,
-    _stream: Stream,
+    source: Box<dyn AudioSource>,
+    spectral: SpectralFrontEnd,          // FFT plan and window, built once for the source's rate
+    mel: Option<MelFilterbank>,          // For the mel-based encodings
 }
 
 impl AudioEncoder {
-    /// Initialize audio encoder with specified encoding strategy
+    /// Initialize audio encoder on the default input device with specified encoding strategy
     pub fn new(encoding: AudioEncoding) -> Result<Self, Box<dyn std::error::Error>> {
-        let host = cpal::default_host();
-        let device = host.default_input_device()
-            .ok_or("no input device available")?;
-
-        let config = device.default_input_config()?;
-        let sample_rate = config.sample_rate().0;
-
-        let buffer = Arc::new(Mutex::new(Vec::new()));
-        let buffer_clone = Arc::clone(&buffer);
-
-        let stream = device.build_input_stream(
-            &config.into(),
-            move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                let mut buf = buffer_clone.lock().unwrap();
-                buf.extend_from_slice(data);
-
-                // Keep buffer from growing unbounded (1 second max)
-                let max_len = sample_rate as usize;
-                if buf.len() > max_len {
-                    let drain_count = buf.len() - max_len;
-                    buf.drain(0..drain_count);
-                }
-            },
-            |err| eprintln!("audio stream error: {}", err),
-            None,
-        )?;
-
-        stream.play()?;
-
-        Ok(AudioEncoder {
-            encoding,
-            sample_rate,
-            buffer,
-            _stream: stream,
-        })
+        Ok(Self::with_source(encoding, Box::new(Microphone::open()?)))
     }
 
-    /// Encode current audio buffer to string
-    pub fn encode_current(&self) -> String {
-        let samples = {
-            let buf = self.buffer.lock().unwrap();
-            buf.clone()
+    /// Read an audio file as 1 second windows every 250ms
+    /// (4 second windows every second for onsets, long enough to find a tempo)
+    pub fn from_file<P: AsRef<Path>>(path: P, encoding: AudioEncoding) -> Result<Self, Box<dyn std::error::Error>> {
+        let (window_ms, hop_ms) = match encoding {
+            AudioEncoding::OnsetEvents => (4000, 1000),
+            _ => (1000, 250),
+        };
+        Ok(Self::with_source(encoding, Box::new(AudioFile::open(path, window_ms, hop_ms)?)))
+    }
+
+    pub fn with_source(encoding: AudioEncoding, source: Box<dyn AudioSource>) -> Self {
+        let spectral = match encoding {
+            // Finer bins to tell neighbouring low notes apart
+            AudioEncoding::PitchDetection { .. } => SpectralFrontEnd::new(source.sample_rate(), 8192, 2048, WindowFunction::Hann),
+            _ => SpectralFrontEnd::default_for(source.sample_rate()),
         };
+        let nyquist = source.sample_rate() as f32 / 2.0;
+        let mel = match encoding {
+            AudioEncoding::BandSymbols { bands } | AudioEncoding::MelBands { bands } =>
+                Some(MelFilterbank::new(bands, &spectral, MEL_MIN_HZ, nyquist)),
+            AudioEncoding::Mfcc { .. } =>
+                Some(MelFilterbank::new(MFCC_MEL_BANDS, &spectral, MEL_MIN_HZ, nyquist)),
+            _ => None,
+        };
+
+        AudioEncoder { encoding, source, spectral, mel }
+    }
+
+    /// Encode current audio buffer to string
+    pub fn encode_current(&mut self) -> String {
+        self.encode_next().unwrap_or_else(|| "audio:end".to_string())
+    }
+
+    /// Encode the source's next window, None once a file source is exhausted
+    pub fn encode_next(&mut self) -> Option<String> {
+        let samples = self.source.next_window()?;
+        Some(self.encode_samples(&samples))
+    }
 
+    fn encode_samples(&self, samples: &[f32]) -> String {
         if samples.is_empty() {
             return "audio:silence".to_string();
         }
 
+        let sample_rate = self.source.sample_rate();
+
         match self.encoding {
             AudioEncoding::FrequencyBins { bin_count } => {
-                encode_fft_bins(&samples, bin_count)
+                encode_fft_bins(&self.spectral, samples, bin_count)
+            }
+            AudioEncoding::BandSymbols { .. } => {
+                encode_band_symbols(&self.spectral, self.mel_filterbank(), samples)
+            }
+            AudioEncoding::MelBands { .. } => {
+                encode_mel_bands(&self.spectral, self.mel_filterbank(), samples)
             }
-            AudioEncoding::BandSymbols { bands } => {
-                encode_band_symbols(&samples, bands)
+            AudioEncoding::Mfcc { coefficients } => {
+                encode_mfcc(&self.spectral, self.mel_filterbank(), samples, coefficients)
             }
             AudioEncoding::OnsetEvents => {
-                encode_onset_events(&samples)
+                encode_onset_events(&self.spectral, samples)
             }
             AudioEncoding::AmplitudeEnvelope { sample_rate_ms } => {
-                encode_amplitude_envelope(&samples, self.sample_rate, sample_rate_ms)
+                encode_amplitude_envelope(samples, sample_rate, sample_rate_ms)
             }
-            AudioEncoding::PitchDetection => {
-                encode_pitch(&samples, self.sample_rate)
+            AudioEncoding::PitchDetection { min_hz, max_hz, peaks } => {
+                encode_pitch(&self.spectral, samples, min_hz, max_hz, peaks)
             }
         }
     }
-}
-
-/// Perform FFT and encode frequency bins
-fn encode_fft_bins(samples: &[f32], bin_count: usize) -> String {
-    let mut planner = FftPlanner::new();
-    let fft_size = samples.len().min(2048).next_power_of_two();
-    let fft = planner.plan_fft_forward(fft_size);
 
-    let mut buffer: Vec<Complex<f32>> = samples
-        .iter()
-        .take(fft_size)
-        .map(|&s| Complex::new(s, 0.0))
-        .collect();
+    /// Numeric form of the source's next window, for FractalMind::process_sensory.
+    /// FrequencyBins gives its bin magnitudes and the mel encodings their band levels
+    /// (-80..0 dB as 0..1); other encodings give 32 log-spaced FFT bin magnitudes.
+    pub fn next_vector(&mut self) -> Option<SensoryVector> {
+        let samples = self.source.next_window()?;
+        Some(self.vector_from_samples(&samples))
+    }
 
-    // Pad if needed
-    while buffer.len() < fft_size {
-        buffer.push(Complex::new(0.0, 0.0));
+    fn vector_from_samples(&self, samples: &[f32]) -> SensoryVector {
+        match self.encoding {
+            AudioEncoding::BandSymbols { .. } | AudioEncoding::MelBands { .. } => {
+                let mel = self.mel_filterbank();
+                let levels = if samples.is_empty() {
+                    vec![0.0; mel.bands()]
+                } else {
+                    mel.apply(&self.spectral.average_power_spectrum(samples))
+                        .into_iter()
+                        .map(|energy| (power_to_db(energy) / VECTOR_DB_RANGE + 1.0).clamp(0.0, 1.0))
+                        .collect()
+                };
+                SensoryVector::new("mel", levels)
+            }
+            encoding => {
+                let bin_count = match encoding {
+                    AudioEncoding::FrequencyBins { bin_count } => bin_count,
+                    _ => VECTOR_FFT_BINS,
+                };
+                let magnitudes = if samples.is_empty() {
+                    vec![0.0; bin_count]
+                } else {
+                    fft_bin_magnitudes(&self.spectral, samples, bin_count)
+                };
+                SensoryVector::new("fft", magnitudes)
+            }
+        }
     }
 
-    fft.process(&mut buffer);
+    fn mel_filterbank(&self) -> &MelFilterbank {
+        self.mel.as_ref().expect("mel filterbank is built for every mel-based encoding")
+    }
+}
 
+/// Windowed, averaged FFT magnitudes grouped into log-spaced bins from 20Hz to Nyquist
+fn encode_fft_bins(spectral: &SpectralFrontEnd, samples: &[f32], bin_count: usize) -> String {
     let mut output = String::from("fft:");
-    let step = fft_size / 2 / bin_count;
-
-    for i in 0..bin_count {
-        let idx = (i * step).min(fft_size / 2 - 1);
-        let magnitude = (buffer[idx].re.powi(2) + buffer[idx].im.powi(2)).sqrt();
+    for magnitude in fft_bin_magnitudes(spectral, samples, bin_count) {
         output.push_str(&format!("{:.2},", magnitude));
     }
-
     output
 }
 
-/// Encode frequency bands as symbols
-fn encode_band_symbols(samples: &[f32], band_count: usize) -> String {
-    let mut planner = FftPlanner::new();
-    let fft_size = samples.len().min(2048).next_power_of_two();
-    let fft = planner.plan_fft_forward(fft_size);
+fn fft_bin_magnitudes(spectral: &SpectralFrontEnd, samples: &[f32], bin_count: usize) -> Vec<f32> {
+    let power = spectral.average_power_spectrum(samples);
 
-    let mut buffer: Vec<Complex<f32>> = samples
-        .iter()
-        .take(fft_size)
-        .map(|&s| Complex::new(s, 0.0))
-        .collect();
+    let nyquist = spectral.sample_rate as f32 / 2.0;
+    let edge = |i: usize| MEL_MIN_HZ * (nyquist / MEL_MIN_HZ).powf(i as f32 / bin_count as f32);
+    let to_bin = |hz: f32| ((hz / nyquist) * (power.len() - 1) as f32).round() as usize;
 
-    while buffer.len() < fft_size {
-        buffer.push(Complex::new(0.0, 0.0));
-    }
+    (0..bin_count)
+        .map(|i| {
+            let start = to_bin(edge(i));
+            let end = to_bin(edge(i + 1)).max(start + 1).min(power.len());
+            let start = start.min(end - 1);
 
-    fft.process(&mut buffer);
+            let mean_power = power[start..end].iter().sum::<f32>() / (end - start) as f32;
+            mean_power.sqrt()
+        })
+        .collect()
+}
 
+/// Encode mel band loudness as symbols
+fn encode_band_symbols(spectral: &SpectralFrontEnd, mel: &MelFilterbank, samples: &[f32]) -> String {
+    let energies = mel.apply(&spectral.average_power_spectrum(samples));
     let mut output = String::from("bands:");
-    let bins_per_band = (fft_size / 2) / band_count;
-
-    for band in 0..band_count {
-        let start = band * bins_per_band;
-        let end = ((band + 1) * bins_per_band).min(fft_size / 2);
 
-        let mut energy = 0.0;
-        for i in start..end {
-            energy += buffer[i].re.powi(2) + buffer[i].im.powi(2);
-        }
-        energy = (energy / (end - start) as f32).sqrt();
-
-        let symbol = match (energy * 100.0) as u32 {
-            0..=5 => '.',
-            6..=15 => '-',
-            16..=30 => '|',
-            31..=50 => '#',
+    for energy in energies {
+        let symbol = match power_to_db(energy) {
+            db if db < -60.0 => '.',
+            db if db < -45.0 => '-',
+            db if db < -30.0 => '|',
+            db if db < -15.0 => '#',
             _ => '@',
         };
         output.push(symbol);
@@ -179,37 +218,57 @@ fn encode_band_symbols(samples: &[f32], band_count: usize) -> String {
     output
 }
 
-/// Detect onsets/beats using amplitude spikes
-fn encode_onset_events(samples: &[f32]) -> String {
-    let window_size = 512;
-    let mut output = String::from("onset:");
+/// Encode mel band energies in whole dB
+fn encode_mel_bands(spectral: &SpectralFrontEnd, mel: &MelFilterbank, samples: &[f32]) -> String {
+    let energies = mel.apply(&spectral.average_power_spectrum(samples));
+    let mut output = String::from("mel:");
 
-    if samples.len() < window_size * 2 {
-        return output;
+    for energy in energies {
+        output.push_str(&format!("{},", power_to_db(energy).round() as i32));
     }
 
-    let mut prev_energy = 0.0;
-
-    for chunk in samples.chunks(window_size) {
-        let energy: f32 = chunk.iter().map(|&s| s.abs()).sum();
-        let energy = energy / chunk.len() as f32;
+    output
+}
 
-        if energy > prev_energy * 1.5 && energy > 0.1 {
-            // Onset detected
-            let intensity = match (energy * 100.0) as u32 {
-                0..=10 => "soft",
-                11..=30 => "med",
-                _ => "hard",
-            };
-            output.push_str(&format!("{},", intensity));
-        }
+/// Encode MFCCs of the average mel spectrum, scaled x10 and rounded so near-identical timbres share a string
+fn encode_mfcc(spectral: &SpectralFrontEnd, mel: &MelFilterbank, samples: &[f32], coefficients: usize) -> String {
+    let energies = mel.apply(&spectral.average_power_spectrum(samples));
+    let mut output = String::from("mfcc:");
 
-        prev_energy = energy;
+    for c in mfcc(&energies, coefficients) {
+        output.push_str(&format!("{},", (c * 10.0).round() as i32));
     }
 
     output
 }
 
+/// Spectral-flux onsets as a rhythm: classified hits separated by sixteenth-note gaps
+fn encode_onset_events(spectral: &SpectralFrontEnd, samples: &[f32]) -> String {
+    let analysis = onset::analyze(samples, spectral);
+
+    let mut output = match analysis.tempo {
+        Some(bpm) => format!("onset:bpm{}|", (bpm / 5.0).round() as u32 * 5),
+        None => "onset:bpm?|".to_string(),
+    };
+
+    // A sixteenth note is a quarter of a beat; gaps are capped at one 4/4 bar
+    let sixteenth = analysis.tempo.map(|bpm| 15.0 / bpm);
+    let hits: Vec<String> = analysis.onsets.iter()
+        .enumerate()
+        .map(|(i, event)| {
+            let gap = analysis.onsets.get(i + 1).zip(sixteenth)
+                .map(|(next, step)| (((next.time - event.time) / step).round() as u32).clamp(1, 16));
+            match gap {
+                Some(gap) => format!("{}:{}", event.hit.as_str(), gap),
+                None => event.hit.as_str().to_string(),
+            }
+        })
+        .collect();
+
+    output.push_str(&hits.join(","));
+    output
+}
+
 /// Encode amplitude envelope at specified sample rate
 fn encode_amplitude_envelope(samples: &[f32], sample_rate: u32, sample_rate_ms: usize) -> String {
     let samples_per_step = (sample_rate as usize * sample_rate_ms) / 1000;
@@ -225,33 +284,38 @@ fn encode_amplitude_envelope(samples: &[f32], sample_rate: u32, sample_rate_ms:
     output
 }
 
-/// Basic pitch detection using zero-crossing rate and autocorrelation
-fn encode_pitch(samples: &[f32], sample_rate: u32) -> String {
-    if samples.len() < 1024 {
-        return "pitch:none".to_string();
-    }
-
-    // Simple autocorrelation-based pitch detection
-    let window = &samples[samples.len() - 1024..];
-
-    let mut max_corr = 0.0;
-    let mut best_lag = 0;
+/// YIN pitch as a note name, then the top spectral peaks as note names in ascending order
+fn encode_pitch(spectral: &SpectralFrontEnd, samples: &[f32], min_hz: f32, max_hz: f32, peak_count: usize) -> String {
+    let pitch = yin(samples, spectral.sample_rate, min_hz, max_hz)
+        .filter(|estimate| estimate.confidence >= MIN_PITCH_CONFIDENCE)
+        .and_then(|estimate| Note::from_frequency(estimate.frequency));
 
-    for lag in 20..500 {
-        let mut corr = 0.0;
-        for i in 0..(window.len() - lag) {
-            corr += window[i] * window[i + lag];
+    let mut output = match pitch {
+        Some(note) => {
+            let note = Note { cents: (note.cents as f32 / 10.0).round() as i32 * 10, ..note };
+            format!("pitch:{}", note)
         }
-        if corr > max_corr {
-            max_corr = corr;
-            best_lag = lag;
+        None => "pitch:none".to_string(),
+    };
+
+    if peak_count > 0 {
+        let power = spectral.average_power_spectrum(samples);
+        let mut peaks = spectral_peaks(&power, spectral, min_hz, max_hz, peak_count);
+        peaks.sort_by(f32::total_cmp);
+
+        let mut names: Vec<String> = Vec::new();
+        for note in peaks.into_iter().filter_map(Note::from_frequency) {
+            let name = format!("{}{}", note.name, note.octave);
+            if !names.contains(&name) {
+                names.push(name);
+            }
         }
-    }
 
-    if max_corr > 0.1 {
-        let frequency = sample_rate as f32 / best_lag as f32;
-        format!("pitch:{:.1}Hz", frequency)
-    } else {
-        "pitch:none".to_string()
+        if !names.is_empty() {
+            output.push_str("|peaks:");
+            output.push_str(&names.join(","));
+        }
     }
+
+    output
 }
@@ -0,0 +1,269 @@
+// Audio sources for AudioEncoder
+// The live microphone, or an audio file read as a sliding window,
+// so audio training also works on headless machines.
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::Stream;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Yields windows of mono samples in [-1, 1]
+pub trait AudioSource {
+    fn sample_rate(&self) -> u32;
+
+    /// Next window of samples, None once the source is exhausted
+    fn next_window(&mut self) -> Option<Vec<f32>>;
+}
+
+/// Default input device - every window is the most recent second of audio
+pub struct Microphone {
+    sample_rate: u32,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    _stream: Stream,
+}
+
+impl Microphone {
+    pub fn open() -> Result<Self, Box<dyn std::error::Error>> {
+        let host = cpal::default_host();
+        let device = host.default_input_device()
+            .ok_or("no input device available")?;
+
+        let config = device.default_input_config()?;
+        let sample_rate = config.sample_rate().0;
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let buffer_clone = Arc::clone(&buffer);
+
+        let stream = device.build_input_stream(
+            &config.into(),
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                let mut buf = buffer_clone.lock().unwrap();
+                buf.extend_from_slice(data);
+
+                // Keep buffer from growing unbounded (1 second max)
+                let max_len = sample_rate as usize;
+                if buf.len() > max_len {
+                    let drain_count = buf.len() - max_len;
+                    buf.drain(0..drain_count);
+                }
+            },
+            |err| eprintln!("audio stream error: {}", err),
+            None,
+        )?;
+
+        stream.play()?;
+
+        Ok(Microphone { sample_rate, buffer, _stream: stream })
+    }
+}
+
+impl AudioSource for Microphone {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn next_window(&mut self) -> Option<Vec<f32>> {
+        Some(self.buffer.lock().unwrap().clone())
+    }
+}
+
+/// A decoded audio file (mixed to mono) read as overlapping windows
+pub struct AudioFile {
+    samples: Vec<f32>,
+    sample_rate: u32,
+    window: usize,
+    hop: usize,
+    position: usize,
+    done: bool,
+}
+
+impl AudioFile {
+    /// Decode a WAV file (FLAC/OGG with the `flac`/`ogg` features) and slide a
+    /// window_ms window across it in hop_ms steps
+    pub fn open<P: AsRef<Path>>(path: P, window_ms: usize, hop_ms: usize) -> Result<Self, Box<dyn std::error::Error>> {
+        let (samples, sample_rate) = decode_audio_file(path.as_ref())?;
+        Ok(Self::from_samples(samples, sample_rate, window_ms, hop_ms))
+    }
+
+    /// Window already-decoded mono samples (also handy for synthetic test signals)
+    pub fn from_samples(samples: Vec<f32>, sample_rate: u32, window_ms: usize, hop_ms: usize) -> Self {
+        let ms_to_samples = |ms: usize| (sample_rate as usize * ms / 1000).max(1);
+        AudioFile {
+            done: samples.is_empty(),
+            samples,
+            sample_rate,
+            window: ms_to_samples(window_ms),
+            hop: ms_to_samples(hop_ms),
+            position: 0,
+        }
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+}
+
+impl AudioSource for AudioFile {
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn next_window(&mut self) -> Option<Vec<f32>> {
+        if self.done {
+            return None;
+        }
+
+        // The last window is the one that reaches the end of the file
+        let end = (self.position + self.window).min(self.samples.len());
+        let window = self.samples[self.position..end].to_vec();
+        self.done = end == self.samples.len();
+        self.position += self.hop;
+
+        Some(window)
+    }
+}
+
+/// Decode an audio file to mono f32 samples, picking the decoder from the extension
+pub fn decode_audio_file(path: &Path) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error>> {
+    let ext = path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+
+    match ext.as_str() {
+        "wav" | "wave" => decode_wav(path),
+        #[cfg(feature = "flac")]
+        "flac" => decode_flac(path),
+        #[cfg(feature = "ogg")]
+        "ogg" | "oga" => decode_ogg(path),
+        _ => Err(format!(
+            "{}: unsupported audio format (WAV is built in; FLAC and OGG need the `flac`/`ogg` features)",
+            path.display()
+        ).into()),
+    }
+}
+
+fn decode_wav(path: &Path) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error>> {
+    let mut reader = hound::WavReader::open(path)?;
+    let spec = reader.spec();
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1u64 << (spec.bits_per_sample - 1)) as f32;
+            reader.samples::<i32>()
+                .map(|s| s.map(|s| s as f32 * scale))
+                .collect::<Result<_, _>>()?
+        }
+    };
+
+    Ok((mix_to_mono(&interleaved, spec.channels as usize), spec.sample_rate))
+}
+
+#[cfg(feature = "flac")]
+fn decode_flac(path: &Path) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error>> {
+    let mut reader = claxon::FlacReader::open(path)?;
+    let info = reader.streaminfo();
+    let scale = 1.0 / (1u64 << (info.bits_per_sample - 1)) as f32;
+
+    let interleaved: Vec<f32> = reader.samples()
+        .map(|s| s.map(|s| s as f32 * scale))
+        .collect::<Result<_, _>>()?;
+
+    Ok((mix_to_mono(&interleaved, info.channels as usize), info.sample_rate))
+}
+
+#[cfg(feature = "ogg")]
+fn decode_ogg(path: &Path) -> Result<(Vec<f32>, u32), Box<dyn std::error::Error>> {
+    let mut reader = lewton::inside_ogg::OggStreamReader::new(std::fs::File::open(path)?)?;
+    let channels = reader.ident_hdr.audio_channels as usize;
+    let sample_rate = reader.ident_hdr.audio_sample_rate;
+
+    let mut interleaved = Vec::new();
+    while let Some(packet) = reader.read_dec_packet_itl()? {
+        interleaved.extend(packet.into_iter().map(|s| s as f32 / 32768.0));
+    }
+
+    Ok((mix_to_mono(&interleaved, channels), sample_rate))
+}
+
+fn mix_to_mono(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved.chunks_exact(channels)
+        .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_wav(path: &Path, channels: u16, samples: &[i16]) {
+        let spec = hound::WavSpec { channels, sample_rate: 8000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for &sample in samples {
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    fn all_windows(mut source: AudioFile) -> Vec<Vec<f32>> {
+        std::iter::from_fn(|| source.next_window()).collect()
+    }
+
+    #[test]
+    fn wav_samples_are_scaled_and_windowed() {
+        let dir = std::env::temp_dir().join(format!("fractal_audio_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mono = dir.join("mono.wav");
+        let stereo = dir.join("stereo.wav");
+        let pattern = [i16::MIN, -16384, 0, 16384, i16::MAX];
+        write_wav(&mono, 1, &pattern.repeat(1600));
+        write_wav(&stereo, 2, &[16384, 0].repeat(8000));
+
+        let mono_file = AudioFile::open(&mono, 100, 50);
+        let (stereo_samples, stereo_rate) = decode_audio_file(&stereo).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let mono_file = mono_file.unwrap();
+        assert_eq!(mono_file.sample_rate(), 8000);
+        assert_eq!(mono_file.duration_secs(), 1.0);
+        let windows = all_windows(mono_file);
+        // 800-sample windows every 400 samples, the last one reaching sample 8000
+        assert_eq!(windows.len(), 19);
+        assert!(windows.iter().all(|w| w.len() == 800));
+        assert_eq!(&windows[0][..5], &[-1.0, -0.5, 0.0, 0.5, 32767.0 / 32768.0]);
+        assert!(windows.iter().flatten().all(|s| (-1.0..=1.0).contains(s)));
+
+        // Channels are mixed to mono
+        assert_eq!(stereo_rate, 8000);
+        assert_eq!(stereo_samples.len(), 8000);
+        assert!(stereo_samples.iter().all(|&s| s == 0.25));
+    }
+
+    #[test]
+    fn windows_overlap_and_keep_a_short_tail() {
+        let samples: Vec<f32> = (0..1000).map(|i| i as f32).collect();
+        let windows = all_windows(AudioFile::from_samples(samples, 1000, 100, 40));
+
+        assert_eq!(windows[0].len(), 100);
+        assert_eq!(windows[1][0], 40.0);
+        assert_eq!(windows[0][40..], windows[1][..60]);
+
+        // 920 is the first start whose window reaches the end: a 80-sample tail
+        assert_eq!(windows.len(), 24);
+        assert_eq!(windows.last().unwrap().len(), 80);
+        assert_eq!(*windows.last().unwrap().last().unwrap(), 999.0);
+
+        let short = all_windows(AudioFile::from_samples(vec![0.5; 30], 1000, 100, 40));
+        assert_eq!(short, [vec![0.5; 30]]);
+        assert!(all_windows(AudioFile::from_samples(Vec::new(), 1000, 100, 40)).is_empty());
+    }
+
+    #[test]
+    fn unknown_extensions_are_rejected() {
+        assert!(decode_audio_file(Path::new("song.mp3")).is_err());
+    }
+}
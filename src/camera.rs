@@ -1,17 +1,25 @@
 // Camera frame encoding for fractal mind training
 // Converts video frames into string representations that hash to Mandelbrot coordinates
 //
-// Note: Camera capture requires system access to /dev/video*.
+// Note: Camera capture requires system access to /dev/video* and the `v4l2` feature.
 // On WSL2, ensure USB passthrough is configured for webcams.
 
+use image::{DynamicImage, RgbImage};
+
+use crate::edges::{encode_edges, EdgeOptions};
+use crate::frame_source::FrameSource;
+use crate::image::pixel_grid_vector;
+use crate::sensory::SensoryVector;
+
 /// Encoding strategy for camera frames
 #[derive(Clone, Copy, Debug)]
 pub enum FrameEncoding {
     /// Downsampled pixel grid (e.g., "128,45,200,130,46,198,...")
     PixelGrid { width: usize, height: usize },
 
-    /// Edge detection symbols (e.g., "|||--|||---||")
-    EdgeSymbols,
+    /// Sobel edges as orientation glyphs on a width x height grid, like ImageEncoding::EdgeAscii
+    /// (e.g., " .-- \n |  /\n")
+    EdgeSymbols { width: usize, height: usize },
 
     /// Average color blocks (e.g., "rgb:128,45,200|rgb:130,46,198|...")
     ColorBlocks { blocks_x: usize, blocks_y: usize },
@@ -20,40 +28,107 @@ pub enum FrameEncoding {
     MotionVectors,
 }
 
-/// Captures frames from camera and encodes them as strings
-/// Currently a stub - requires v4l2/libclang dependencies for real camera access
+/// Default capture device for CameraEncoder::new
+pub const DEFAULT_DEVICE: &str = "/dev/video0";
+
+/// Blocks per side in the numeric form of encodings that have none of their own
+const VECTOR_BLOCKS: usize = 8;
+
+/// Pulls frames from a FrameSource and encodes them as strings
 pub struct CameraEncoder {
     encoding: FrameEncoding,
+    source: Box<dyn FrameSource>,
+    last_frame: Option<Vec<u8>>,   // Previous frame, for MotionVectors
 }
 
 impl CameraEncoder {
-    /// Initialize camera encoder with specified encoding strategy
+    /// Initialize camera encoder on the default webcam with specified encoding strategy
     pub fn new(encoding: FrameEncoding) -> Result<Self, Box<dyn std::error::Error>> {
-        // TODO: Implement camera capture using v4l2 bindings or ffmpeg
-        // For now, return error with helpful message
+        Self::open_device(encoding, DEFAULT_DEVICE)
+    }
+
+    /// Capture from a specific /dev/video* node (v4l2loopback devices work too)
+    #[cfg(all(feature = "v4l2", target_os = "linux"))]
+    pub fn open_device(encoding: FrameEncoding, path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let camera = crate::v4l2::V4l2Camera::open(path, 640, 480)?;
+        Ok(Self::with_source(encoding, Box::new(camera)))
+    }
+
+    #[cfg(not(all(feature = "v4l2", target_os = "linux")))]
+    pub fn open_device(encoding: FrameEncoding, path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         Err(format!(
-            "Camera capture not yet implemented.\n\
+            "Camera capture from {} needs Linux and the `v4l2` feature.\n\
              To use camera:\n\
-             1. Extract frames with: ffmpeg -i /dev/video0 -r 10 frame_%04d.png\n\
-             2. Use /image command to process frames\n\
+             1. Rebuild with: cargo run --features v4l2\n\
+             2. Or extract frames with: ffmpeg -i /dev/video0 -r 10 frame_%04d.png\n\
+             \x20  and use /image to process them\n\
              \n\
              Encoding strategy: {:?}",
-            encoding
+            path, encoding
         ).into())
     }
 
+    /// Encode frames from any source - files, loopback devices, generators
+    pub fn with_source(encoding: FrameEncoding, source: Box<dyn FrameSource>) -> Self {
+        CameraEncoder {
+            encoding,
+            source,
+            last_frame: None,
+        }
+    }
+
     /// Capture and encode a single frame
     pub fn capture_frame(&mut self) -> Result<String, Box<dyn std::error::Error>> {
-        Err("Camera capture not implemented - use ffmpeg to extract frames".into())
+        let frame = self.source.next_frame()?;
+        let (buffer, width, height) = (&frame.data, frame.width, frame.height);
+
+        let encoded = match self.encoding {
+            FrameEncoding::PixelGrid { width: target_w, height: target_h } =>
+                encode_pixel_grid(buffer, width, height, target_w, target_h),
+            FrameEncoding::EdgeSymbols { width: target_w, height: target_h } =>
+                encode_edge_symbols(buffer, width, height, target_w, target_h),
+            FrameEncoding::ColorBlocks { blocks_x, blocks_y } =>
+                encode_color_blocks(buffer, width, height, blocks_x, blocks_y),
+            FrameEncoding::MotionVectors => encode_motion_vectors(buffer, &self.last_frame, width, height),
+        };
+
+        self.last_frame = Some(frame.data);
+        Ok(encoded)
+    }
+
+    /// Capture a frame as numbers for FractalMind::process_sensory. PixelGrid gives R, G, B
+    /// in 0..1 per grid pixel (filtered like image::pixel_grid_vector, so frames and image
+    /// files of a scene land alike), ColorBlocks the mean color of each block, and the other
+    /// encodings 8x8 color blocks.
+    pub fn capture_vector(&mut self) -> Result<SensoryVector, Box<dyn std::error::Error>> {
+        let frame = self.source.next_frame()?;
+        let (buffer, width, height) = (&frame.data, frame.width, frame.height);
+
+        let vector = match self.encoding {
+            FrameEncoding::PixelGrid { width: target_w, height: target_h } => {
+                let rgb = RgbImage::from_raw(width as u32, height as u32, buffer.clone())
+                    .ok_or("frame buffer is smaller than its dimensions")?;
+                pixel_grid_vector(&DynamicImage::ImageRgb8(rgb), target_w, target_h)
+            }
+            FrameEncoding::ColorBlocks { blocks_x, blocks_y } =>
+                color_block_vector(buffer, width, height, blocks_x, blocks_y),
+            _ => color_block_vector(buffer, width, height, VECTOR_BLOCKS, VECTOR_BLOCKS),
+        };
+
+        self.last_frame = Some(frame.data);
+        Ok(vector)
     }
 }
 
 /// Downsample frame to grid and encode as comma-separated RGB values
 fn encode_pixel_grid(buffer: &[u8], width: usize, height: usize, target_w: usize, target_h: usize) -> String {
     let mut output = String::with_capacity(target_w * target_h * 12);
+    if target_w == 0 || target_h == 0 || width == 0 || height == 0 {
+        return output;
+    }
 
-    let step_x = width / target_w;
-    let step_y = height / target_h;
+    let step_x = (width / target_w).max(1);
+    let step_y = (height / target_h).max(1);
 
     for y in 0..target_h {
         for x in 0..target_w {
@@ -73,52 +148,46 @@ fn encode_pixel_grid(buffer: &[u8], width: usize, height: usize, target_w: usize
     output
 }
 
-/// Detect edges using simple gradient and encode as symbols
-fn encode_edges(buffer: &[u8], width: usize, height: usize) -> String {
-    let mut output = String::with_capacity(width * height / 100);
-
-    let step = 8; // Sample every 8 pixels
+/// Same edge glyphs as ImageEncoding::EdgeAscii, so camera frames and image files of a
+/// scene encode alike
+fn encode_edge_symbols(buffer: &[u8], width: usize, height: usize, target_w: usize, target_h: usize) -> String {
+    let Some(rgb) = RgbImage::from_raw(width as u32, height as u32, buffer.to_vec()) else {
+        return String::new();
+    };
+    let gray = DynamicImage::ImageRgb8(rgb).to_luma8();
+    encode_edges(&gray, target_w, target_h, EdgeOptions::default())
+}
 
-    for y in (step..height - step).step_by(step) {
-        for x in (step..width - step).step_by(step) {
-            let idx = (y * width + x) * 3;
+/// Divide frame into blocks and encode average color
+fn encode_color_blocks(buffer: &[u8], width: usize, height: usize, blocks_x: usize, blocks_y: usize) -> String {
+    let mut output = String::with_capacity(blocks_x * blocks_y * 20);
 
-            if idx + 3 < buffer.len() {
-                let center = buffer[idx] as i32;
-                let right_idx = (y * width + x + step) * 3;
-                let down_idx = ((y + step) * width + x) * 3;
-
-                if right_idx + 2 < buffer.len() && down_idx + 2 < buffer.len() {
-                    let right = buffer[right_idx] as i32;
-                    let down = buffer[down_idx] as i32;
-
-                    let gradient_x = (right - center).abs();
-                    let gradient_y = (down - center).abs();
-                    let gradient = gradient_x + gradient_y;
-
-                    let symbol = match gradient {
-                        0..=20 => ' ',
-                        21..=50 => '.',
-                        51..=100 => '-',
-                        101..=150 => '|',
-                        _ => '#',
-                    };
-                    output.push(symbol);
-                }
-            }
-        }
-        output.push('\n');
+    for [r_avg, g_avg, b_avg] in color_block_means(buffer, width, height, blocks_x, blocks_y) {
+        output.push_str(&format!("rgb:{},{},{}|", r_avg, g_avg, b_avg));
     }
 
     output
 }
 
-/// Divide frame into blocks and encode average color
-fn encode_color_blocks(buffer: &[u8], width: usize, height: usize, blocks_x: usize, blocks_y: usize) -> String {
-    let mut output = String::with_capacity(blocks_x * blocks_y * 20);
+/// Average colors as R, G, B in 0..1 per block
+fn color_block_vector(buffer: &[u8], width: usize, height: usize, blocks_x: usize, blocks_y: usize) -> SensoryVector {
+    let values = color_block_means(buffer, width, height, blocks_x, blocks_y)
+        .into_iter()
+        .flatten()
+        .map(|c| c as f32 / 255.0)
+        .collect();
+    SensoryVector::new("blocks", values)
+}
 
-    let block_w = width / blocks_x;
-    let block_h = height / blocks_y;
+/// Average color of each block, row by row (blocks with no pixels are left out)
+fn color_block_means(buffer: &[u8], width: usize, height: usize, blocks_x: usize, blocks_y: usize) -> Vec<[u32; 3]> {
+    let mut means = Vec::with_capacity(blocks_x * blocks_y);
+    if blocks_x == 0 || blocks_y == 0 {
+        return means;
+    }
+
+    let block_w = (width / blocks_x).max(1);
+    let block_h = (height / blocks_y).max(1);
 
     for by in 0..blocks_y {
         for bx in 0..blocks_x {
@@ -140,15 +209,12 @@ fn encode_color_blocks(buffer: &[u8], width: usize, height: usize, blocks_x: usi
             }
 
             if count > 0 {
-                let r_avg = r_sum / count;
-                let g_avg = g_sum / count;
-                let b_avg = b_sum / count;
-                output.push_str(&format!("rgb:{},{},{}|", r_avg, g_avg, b_avg));
+                means.push([r_sum / count, g_sum / count, b_sum / count]);
             }
         }
     }
 
-    output
+    means
 }
 
 /// Encode motion between frames as directional vectors
@@ -164,8 +230,8 @@ fn encode_motion_vectors(buffer: &[u8], last_frame: &Option<Vec<u8>>, width: usi
     let mut output = String::from("mv:");
     let step = 16; // Compare 16x16 blocks
 
-    for y in (0..height - step).step_by(step) {
-        for x in (0..width - step).step_by(step) {
+    for y in (0..height.saturating_sub(step)).step_by(step) {
+        for x in (0..width.saturating_sub(step)).step_by(step) {
             let idx = (y * width + x) * 3;
 
             if idx + 2 < buffer.len() && idx + 2 < prev.len() {
@@ -188,3 +254,122 @@ fn encode_motion_vectors(buffer: &[u8], last_frame: &Option<Vec<u8>>, width: usi
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use crate::frame_source::{ImageDirectory, RawRgbFile, SyntheticSource};
+
+    const WIDTH: usize = 64;
+    const HEIGHT: usize = 48;
+
+    const ENCODINGS: [FrameEncoding; 4] = [
+        FrameEncoding::PixelGrid { width: 8, height: 6 },
+        FrameEncoding::EdgeSymbols { width: 16, height: 8 },
+        FrameEncoding::ColorBlocks { blocks_x: 4, blocks_y: 3 },
+        FrameEncoding::MotionVectors,
+    ];
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fractal_camera_test_{}_{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// The first `count` synthetic frames
+    fn synthetic_frames(count: usize) -> Vec<crate::frame_source::Frame> {
+        let mut source = SyntheticSource::new(WIDTH, HEIGHT);
+        (0..count).map(|_| source.next_frame().unwrap()).collect()
+    }
+
+    /// Encode three frames from the source and check each has its encoding's shape
+    fn check_encodings(make_source: impl Fn() -> Box<dyn FrameSource>) {
+        for encoding in ENCODINGS {
+            let mut encoder = CameraEncoder::with_source(encoding, make_source());
+            let encoded: Vec<String> = (0..3).map(|_| encoder.capture_frame().unwrap()).collect();
+
+            for frame in &encoded {
+                match encoding {
+                    FrameEncoding::PixelGrid { width, height } =>
+                        assert_eq!(frame.split(',').filter(|v| !v.is_empty()).count(), width * height * 3),
+                    FrameEncoding::EdgeSymbols { width, height } => {
+                        assert_eq!(frame.lines().count(), height);
+                        assert!(frame.lines().all(|line| line.chars().count() == width));
+                        assert!(frame.contains('|'), "the bars' edges are vertical: {:?}", frame);
+                    }
+                    FrameEncoding::ColorBlocks { blocks_x, blocks_y } =>
+                        assert_eq!(frame.matches("rgb:").count(), blocks_x * blocks_y),
+                    FrameEncoding::MotionVectors => assert!(frame.starts_with("mv:")),
+                }
+            }
+
+            if let FrameEncoding::MotionVectors = encoding {
+                assert_eq!(encoded[0], "mv:init");
+                assert!(encoded[1].chars().skip(3).any(|c| c != '.'), "the pattern moves: {}", encoded[1]);
+            }
+
+            let vector = encoder.capture_vector().unwrap();
+            let expected_len = match encoding {
+                FrameEncoding::PixelGrid { width, height } => width * height * 3,
+                FrameEncoding::ColorBlocks { blocks_x, blocks_y } => blocks_x * blocks_y * 3,
+                _ => VECTOR_BLOCKS * VECTOR_BLOCKS * 3,
+            };
+            assert_eq!(vector.values.len(), expected_len);
+            assert!(vector.values.iter().all(|v| (0.0..=1.0).contains(v)));
+        }
+    }
+
+    #[test]
+    fn encodes_synthetic_frames() {
+        check_encodings(|| Box::new(SyntheticSource::new(WIDTH, HEIGHT)));
+    }
+
+    #[test]
+    fn encodes_image_directory_frames() {
+        let dir = temp_dir("frames");
+        for (i, frame) in synthetic_frames(3).into_iter().enumerate() {
+            RgbImage::from_raw(WIDTH as u32, HEIGHT as u32, frame.data).unwrap()
+                .save(dir.join(format!("frame_{:04}.png", i)))
+                .unwrap();
+        }
+
+        check_encodings(|| Box::new(ImageDirectory::open(&dir, true).unwrap()));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn encodes_raw_rgb_frames() {
+        let dir = temp_dir("raw");
+        let path = dir.join("frames.rgb");
+        let bytes: Vec<u8> = synthetic_frames(3).into_iter().flat_map(|frame| frame.data).collect();
+        std::fs::write(&path, bytes).unwrap();
+
+        check_encodings(|| Box::new(RawRgbFile::open(&path, WIDTH, HEIGHT, true).unwrap()));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn sources_agree_on_the_same_frames() {
+        let dir = temp_dir("agree");
+        let frames = synthetic_frames(2);
+        let raw_path = dir.join("frames.rgb");
+        std::fs::write(&raw_path, frames.iter().flat_map(|f| f.data.clone()).collect::<Vec<u8>>()).unwrap();
+        for (i, frame) in frames.iter().enumerate() {
+            RgbImage::from_raw(WIDTH as u32, HEIGHT as u32, frame.data.clone()).unwrap()
+                .save(dir.join(format!("frame_{:04}.png", i)))
+                .unwrap();
+        }
+
+        let mut raw = RawRgbFile::open(&raw_path, WIDTH, HEIGHT, false).unwrap();
+        let mut images = ImageDirectory::open(&dir, false).unwrap();
+        for frame in &frames {
+            assert_eq!(raw.next_frame().unwrap().data, frame.data);
+            assert_eq!(images.next_frame().unwrap().data, frame.data);
+        }
+        // Neither loops, so both run out
+        assert!(raw.next_frame().is_err());
+        assert!(images.next_frame().is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
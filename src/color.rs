@@ -0,0 +1,221 @@
+// Perceptual color helpers for image encoding
+// sRGB <-> CIELAB conversion and deterministic k-means clustering in Lab,
+// where Euclidean distance roughly matches perceived color difference, plus HSV.
+
+use rand::Rng;
+use crate::rng::MindRng;
+
+/// Fixed k-means++ seed so the same image always gives the same clusters
+const KMEANS_SEED: u64 = 0x6b6d_6561_6e73;
+const KMEANS_MAX_ITERATIONS: usize = 24;
+
+// D65 reference white
+const WHITE: [f32; 3] = [0.950_47, 1.0, 1.088_83];
+
+/// sRGB (8-bit) to CIELAB (L in 0..100, a/b roughly -128..127)
+pub fn srgb_to_lab(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|c| srgb_to_linear(c as f32 / 255.0));
+
+    let x = 0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b;
+    let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+    let z = 0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b;
+
+    let [fx, fy, fz] = [x / WHITE[0], y / WHITE[1], z / WHITE[2]].map(lab_f);
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+/// CIELAB back to sRGB, clamping out-of-gamut colors
+pub fn lab_to_srgb(lab: [f32; 3]) -> [u8; 3] {
+    let fy = (lab[0] + 16.0) / 116.0;
+    let fx = fy + lab[1] / 500.0;
+    let fz = fy - lab[2] / 200.0;
+    let [x, y, z] = [fx, fy, fz].map(lab_f_inverse);
+    let (x, y, z) = (x * WHITE[0], y * WHITE[1], z * WHITE[2]);
+
+    let r = 3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z;
+    let g = -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z;
+    let b = 0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z;
+
+    [r, g, b].map(|c| (linear_to_srgb(c.clamp(0.0, 1.0)) * 255.0).round() as u8)
+}
+
+/// sRGB (8-bit) to HSV, all components in 0..1 (hue 0 for greys)
+pub fn rgb_to_hsv(rgb: [u8; 3]) -> [f32; 3] {
+    let [r, g, b] = rgb.map(|c| c as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    let hue = if chroma <= 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / chroma).rem_euclid(6.0) / 6.0
+    } else if max == g {
+        ((b - r) / chroma + 2.0) / 6.0
+    } else {
+        ((r - g) / chroma + 4.0) / 6.0
+    };
+    let saturation = if max > 0.0 { chroma / max } else { 0.0 };
+
+    [hue, saturation, max]
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.040_45 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 }
+}
+
+fn lab_f(t: f32) -> f32 {
+    const DELTA: f32 = 6.0 / 29.0;
+    if t > DELTA * DELTA * DELTA { t.cbrt() } else { t / (3.0 * DELTA * DELTA) + 4.0 / 29.0 }
+}
+
+fn lab_f_inverse(t: f32) -> f32 {
+    const DELTA: f32 = 6.0 / 29.0;
+    if t > DELTA { t * t * t } else { 3.0 * DELTA * DELTA * (t - 4.0 / 29.0) }
+}
+
+fn distance_sq(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+/// One k-means cluster: its Lab centroid and the share of samples it holds
+#[derive(Clone, Copy, Debug)]
+pub struct ColorCluster {
+    pub lab: [f32; 3],
+    pub weight: f32,   // 0..1, all clusters sum to 1
+}
+
+/// K-means over Lab samples with k-means++ seeding from a fixed seed.
+/// Returns at most k clusters (fewer when there are fewer distinct colors), unordered.
+pub fn kmeans_lab(samples: &[[f32; 3]], k: usize) -> Vec<ColorCluster> {
+    if samples.is_empty() || k == 0 {
+        return Vec::new();
+    }
+
+    let mut rng = MindRng::from_seed(KMEANS_SEED);
+    let mut centroids = vec![samples[rng.gen_range(0..samples.len())]];
+
+    // k-means++: pick each next seed with probability proportional to squared distance
+    let mut nearest: Vec<f32> = samples.iter().map(|&s| distance_sq(s, centroids[0])).collect();
+    while centroids.len() < k {
+        let total: f32 = nearest.iter().sum();
+        if total <= f32::EPSILON {
+            break; // Every sample already coincides with a centroid
+        }
+        let mut target = rng.gen_range(0.0..total);
+        let next = nearest.iter()
+            .position(|&d| { target -= d; target < 0.0 })
+            .unwrap_or(samples.len() - 1);
+        centroids.push(samples[next]);
+        for (d, &s) in nearest.iter_mut().zip(samples) {
+            *d = d.min(distance_sq(s, samples[next]));
+        }
+    }
+
+    let mut assignment = vec![usize::MAX; samples.len()];
+    for _ in 0..KMEANS_MAX_ITERATIONS {
+        let mut changed = false;
+        for (i, &s) in samples.iter().enumerate() {
+            let best = (0..centroids.len())
+                .min_by(|&a, &b| distance_sq(s, centroids[a]).total_cmp(&distance_sq(s, centroids[b])))
+                .unwrap_or(0);
+            if assignment[i] != best {
+                assignment[i] = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+
+        let mut sums = vec![([0.0f32; 3], 0usize); centroids.len()];
+        for (&s, &c) in samples.iter().zip(&assignment) {
+            for (sum, value) in sums[c].0.iter_mut().zip(s) {
+                *sum += value;
+            }
+            sums[c].1 += 1;
+        }
+        for (centroid, (sum, count)) in centroids.iter_mut().zip(sums) {
+            // An emptied cluster keeps its old centroid
+            if count > 0 {
+                *centroid = sum.map(|v| v / count as f32);
+            }
+        }
+    }
+
+    let mut counts = vec![0usize; centroids.len()];
+    for &c in &assignment {
+        counts[c] += 1;
+    }
+
+    centroids.into_iter()
+        .zip(counts)
+        .filter(|&(_, count)| count > 0)
+        .map(|(lab, count)| ColorCluster { lab, weight: count as f32 / samples.len() as f32 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Rgb, RgbImage};
+    use crate::image::{encode_image_data, ImageEncoding};
+
+    const RED: [u8; 3] = [224, 32, 32];
+    const BLUE: [u8; 3] = [32, 48, 208];
+
+    #[test]
+    fn lab_round_trips_to_the_same_srgb() {
+        for r in (0..=255).step_by(15) {
+            for g in (0..=255).step_by(15) {
+                for b in (0..=255).step_by(15) {
+                    let rgb = [r as u8, g as u8, b as u8];
+                    assert_eq!(lab_to_srgb(srgb_to_lab(rgb)), rgb);
+                }
+            }
+        }
+
+        let white = srgb_to_lab([255, 255, 255]);
+        assert!((white[0] - 100.0).abs() < 0.01 && white[1].abs() < 0.01 && white[2].abs() < 0.01);
+        assert!(srgb_to_lab([0, 0, 0]).iter().all(|v| v.abs() < 0.01));
+    }
+
+    #[test]
+    fn two_colors_give_two_stable_clusters() {
+        let (red, blue) = (srgb_to_lab(RED), srgb_to_lab(BLUE));
+        let samples: Vec<[f32; 3]> = (0..100).map(|i| if i % 10 < 7 { red } else { blue }).collect();
+
+        let clusters = kmeans_lab(&samples, 2);
+        assert_eq!(clusters.len(), 2);
+        for (color, weight) in [(red, 0.7), (blue, 0.3)] {
+            let cluster = clusters.iter().find(|c| distance_sq(c.lab, color) < 1e-6).expect("a cluster on each color");
+            assert!((cluster.weight - weight).abs() < 1e-6);
+        }
+
+        let again = kmeans_lab(&samples, 2);
+        let labs = |clusters: &[ColorCluster]| clusters.iter().map(|c| c.lab).collect::<Vec<_>>();
+        assert_eq!(labs(&again), labs(&clusters));
+    }
+
+    #[test]
+    fn extra_clusters_and_empty_input_do_not_panic() {
+        let samples = vec![srgb_to_lab(RED), srgb_to_lab(BLUE), srgb_to_lab(RED)];
+        assert_eq!(kmeans_lab(&samples, 8).len(), 2);
+        assert!(kmeans_lab(&[], 4).is_empty());
+        assert!(kmeans_lab(&samples, 0).is_empty());
+    }
+
+    #[test]
+    fn dominant_colors_of_a_two_color_image() {
+        let img = DynamicImage::ImageRgb8(RgbImage::from_fn(40, 40, |x, _| Rgb(if x < 28 { RED } else { BLUE })));
+        let encoding = ImageEncoding::DominantColors { color_count: 5 };
+
+        let encoded = encode_image_data(&img, encoding);
+        assert_eq!(encoded, "colors:rgb:32,48,208@30|rgb:224,32,32@70|");
+        assert_eq!(encode_image_data(&img, encoding), encoded);
+    }
+}
@@ -0,0 +1,130 @@
+// Tunable hyperparameters for FractalMind
+// Saved with the mind and loadable from a JSON file, so experiments don't need a rebuild
+
+use std::fs;
+use bincode::{Encode, Decode};
+use serde::{Serialize, Deserialize};
+
+/// Every knob the learning and generation loops read.
+/// Missing keys in a config file fall back to the defaults below.
+#[derive(Clone, Debug, Encode, Decode, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MindConfig {
+    // Symbol storage / pruning
+    pub merge_threshold: u32,        // Hamming distance (bits) below which fingerprints merge
+    pub confidence_boost: f32,       // Confidence gained each time a symbol is merged into
+    pub decay_rate: f32,             // Confidence lost per input
+    pub min_confidence: f32,         // Below this, unreferenced rarely-used symbols are pruned
+    pub prune_min_count: u32,        // Symbols used more often than this are never pruned
+
+    // Hierarchical processing
+    pub context_length: usize,       // Inputs kept in context_history
+    pub context_min_intensity: f64,  // Only inputs above this intensity enter context_history
+    pub context_influence: f64,      // Pull of context on the chunk coordinate
+    pub chunk_intensity: f64,        // Level 3: whole input
+    pub word_intensity: f64,         // Level 2: words
+    pub char_intensity: f64,         // Level 1: character stream
+    pub char_stream_limit: usize,    // Max characters in the level 1 stream
+    pub char_min_intensity: f64,     // Level 1 only runs for inputs above this intensity
+
+    // Response generation
+    pub max_response_symbols: usize,
+    pub context_search_weight: f64,     // Probability of searching at the current position
+    pub background_search_weight: f64,  // Probability of blending in the background thought (rest explores)
+    pub background_blend: f64,          // Share of the background thought in a blended search coordinate
+    pub search_radius_factor: f64,      // Candidate radius = exploration_radius * factor, at most ConceptTrajectory::influence_radius()
+    pub explore_radius: f64,            // Exploring searches at an interesting point this close
+    pub explore_samples: usize,         // Points tried to find it
+    pub long_pattern_symbols: usize,    // Trajectories with more symbols than this count as rich
+    pub long_pattern_bonus: f64,        // Candidate weight multiplier for rich trajectories
+    pub short_pattern_bonus: f64,       // ... and for the rest
+    pub field_reach: f64,               // Fields within radius * reach add their trajectories as candidates
+    pub field_influence: f64,           // Their candidate weight = field strength * this
+    pub symbol_inhibition: u32,         // Refractory steps for a used symbol
+    pub trajectory_inhibition: u32,     // Refractory steps for a used trajectory
+    pub hebbian_boost: f64,             // Strength added to trajectories used in a response
+    pub trajectory_decay: f64,          // Strength multiplier applied after every response
+    pub min_trajectory_strength: f64,
+    pub fallback_radius: f64,           // explore_response looks for interesting points this close
+    pub fallback_samples: usize,
+
+    // Background thought
+    pub max_thought_symbols: usize,
+    pub thought_drift: f64,             // Random offset of the starting position
+    pub thought_wander: f64,            // Random offset after each step
+    pub thought_current_weight: f64,    // Probability of searching at the wandering position
+    pub thought_nearby_weight: f64,     // Probability of nearby associations (rest explores)
+    pub thought_nearby_radius: f64,     // Interesting points this close count as nearby associations
+    pub thought_nearby_samples: usize,
+    pub thought_explore_radius: f64,    // Random exploration picks among interesting points this close
+    pub thought_explore_samples: usize,
+    pub thought_radius_factor: f64,     // Likewise for background thoughts
+}
+
+impl Default for MindConfig {
+    fn default() -> Self {
+        MindConfig {
+            merge_threshold: 8,
+            confidence_boost: 0.15,
+            decay_rate: 0.001,
+            min_confidence: 0.05,
+            prune_min_count: 10,
+
+            context_length: 10,
+            context_min_intensity: 0.3,
+            context_influence: 0.7,
+            chunk_intensity: 0.8,
+            word_intensity: 0.4,
+            char_intensity: 0.1,
+            char_stream_limit: 50,
+            char_min_intensity: 0.5,
+
+            max_response_symbols: 100,
+            context_search_weight: 0.85,
+            background_search_weight: 0.10,
+            background_blend: 0.3,
+            // Was 10 (and 15 for thoughts): radii spanning the whole region, so the spatial index
+            // skipped nothing. Trajectories past 0.2 (0.3) - under 45% (17%) of their strength
+            // as influence - are no longer candidates. See benches/trajectory_search.
+            search_radius_factor: 1.0,
+            explore_radius: 0.1,
+            explore_samples: 3,
+            long_pattern_symbols: 5,
+            long_pattern_bonus: 2.0,
+            short_pattern_bonus: 0.5,
+            field_reach: 3.0,
+            field_influence: 0.5,
+            symbol_inhibition: 3,
+            trajectory_inhibition: 2,
+            hebbian_boost: 0.1,
+            trajectory_decay: 0.995,
+            min_trajectory_strength: 0.1,
+            fallback_radius: 0.1,
+            fallback_samples: 30,
+
+            max_thought_symbols: 30,
+            thought_drift: 0.3,
+            thought_wander: 0.1,
+            thought_current_weight: 0.5,
+            thought_nearby_weight: 0.3,
+            thought_nearby_radius: 0.3,
+            thought_nearby_samples: 5,
+            thought_explore_radius: 0.5,
+            thought_explore_samples: 10,
+            thought_radius_factor: 1.5,
+        }
+    }
+}
+
+impl MindConfig {
+    /// Load a (possibly partial) config from a JSON file
+    pub fn from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let data = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&data)?)
+    }
+
+    pub fn save_to_file(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+// Edge detection shared by image and camera encoding
+// Sobel gradients with optional Gaussian blur and non-maximum suppression,
+// rendered as orientation glyphs so the string keeps edge direction.
+
+use image::{GrayImage, imageops};
+
+/// Gradient magnitude below this is no edge at all
+const WEAK_EDGE: f32 = 40.0;
+/// Gradient magnitude from which an edge gets an orientation glyph
+const STRONG_EDGE: f32 = 100.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EdgeOptions {
+    pub blur_sigma: Option<f32>,     // Gaussian pre-blur, None to skip
+    pub non_max_suppression: bool,   // Thin edges to one cell across
+}
+
+impl Default for EdgeOptions {
+    fn default() -> Self {
+        EdgeOptions { blur_sigma: Some(0.8), non_max_suppression: true }
+    }
+}
+
+/// Sobel gradient per pixel: (magnitude, direction in radians)
+pub struct Gradients {
+    pub width: usize,
+    pub height: usize,
+    pub magnitude: Vec<f32>,
+    pub direction: Vec<f32>,
+}
+
+/// Resize to target_w x target_h and render one glyph per cell, one line per row:
+/// ' ' nothing, '.' weak edge, and `-` `|` `/` `\` for the direction of a strong edge
+pub fn encode_edges(gray: &GrayImage, target_w: usize, target_h: usize, options: EdgeOptions) -> String {
+    let mut output = String::with_capacity(target_w * target_h + target_h);
+    if target_w == 0 || target_h == 0 || gray.width() == 0 || gray.height() == 0 {
+        return output;
+    }
+
+    let resized = imageops::resize(gray, target_w as u32, target_h as u32, imageops::FilterType::Triangle);
+    let mut pixels: Vec<f32> = resized.pixels().map(|p| p[0] as f32).collect();
+    if let Some(sigma) = options.blur_sigma {
+        pixels = gaussian_blur(&pixels, target_w, target_h, sigma);
+    }
+
+    let gradients = sobel(&pixels, target_w, target_h);
+    let magnitude = if options.non_max_suppression {
+        non_max_suppression(&gradients)
+    } else {
+        gradients.magnitude.clone()
+    };
+
+    for y in 0..target_h {
+        for x in 0..target_w {
+            let i = y * target_w + x;
+            output.push(edge_glyph(magnitude[i], gradients.direction[i]));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+fn edge_glyph(magnitude: f32, direction: f32) -> char {
+    if magnitude < WEAK_EDGE {
+        return ' ';
+    }
+    if magnitude < STRONG_EDGE {
+        return '.';
+    }
+
+    // The edge runs perpendicular to the gradient; y points down
+    match direction_sector(direction) {
+        0 => '|',
+        1 => '/',
+        2 => '-',
+        _ => '\\',
+    }
+}
+
+/// Gradient direction folded to 0..180 degrees and rounded to 0, 45, 90 or 135 (as 0..=3)
+fn direction_sector(direction: f32) -> usize {
+    let degrees = direction.to_degrees().rem_euclid(180.0);
+    ((degrees + 22.5) / 45.0) as usize % 4
+}
+
+/// 3x3 Sobel operator, edges clamped
+pub fn sobel(pixels: &[f32], width: usize, height: usize) -> Gradients {
+    let at = |x: isize, y: isize| {
+        let x = x.clamp(0, width as isize - 1) as usize;
+        let y = y.clamp(0, height as isize - 1) as usize;
+        pixels[y * width + x]
+    };
+
+    let mut magnitude = Vec::with_capacity(width * height);
+    let mut direction = Vec::with_capacity(width * height);
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let gx = (at(x + 1, y - 1) + 2.0 * at(x + 1, y) + at(x + 1, y + 1))
+                - (at(x - 1, y - 1) + 2.0 * at(x - 1, y) + at(x - 1, y + 1));
+            let gy = (at(x - 1, y + 1) + 2.0 * at(x, y + 1) + at(x + 1, y + 1))
+                - (at(x - 1, y - 1) + 2.0 * at(x, y - 1) + at(x + 1, y - 1));
+            magnitude.push(gx.hypot(gy));
+            direction.push(gy.atan2(gx));
+        }
+    }
+
+    Gradients { width, height, magnitude, direction }
+}
+
+/// Separable Gaussian blur, edges clamped
+pub fn gaussian_blur(pixels: &[f32], width: usize, height: usize, sigma: f32) -> Vec<f32> {
+    if sigma <= 0.0 {
+        return pixels.to_vec();
+    }
+
+    let radius = (3.0 * sigma).ceil() as isize;
+    let kernel: Vec<f32> = (-radius..=radius).map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp()).collect();
+    let total: f32 = kernel.iter().sum();
+    let kernel: Vec<f32> = kernel.iter().map(|k| k / total).collect();
+
+    let pass = |source: &[f32], horizontal: bool| -> Vec<f32> {
+        let mut out = vec![0.0; source.len()];
+        for y in 0..height {
+            for x in 0..width {
+                out[y * width + x] = kernel.iter().enumerate().map(|(k, weight)| {
+                    let offset = k as isize - radius;
+                    let (sx, sy) = if horizontal {
+                        ((x as isize + offset).clamp(0, width as isize - 1) as usize, y)
+                    } else {
+                        (x, (y as isize + offset).clamp(0, height as isize - 1) as usize)
+                    };
+                    source[sy * width + sx] * weight
+                }).sum();
+            }
+        }
+        out
+    };
+
+    pass(&pass(pixels, true), false)
+}
+
+/// Keep only magnitudes that are a maximum across the edge (along the gradient)
+pub fn non_max_suppression(gradients: &Gradients) -> Vec<f32> {
+    let (width, height) = (gradients.width as isize, gradients.height as isize);
+    let magnitude = &gradients.magnitude;
+    let at = |x: isize, y: isize| {
+        if x < 0 || y < 0 || x >= width || y >= height { 0.0 } else { magnitude[(y * width + x) as usize] }
+    };
+
+    (0..magnitude.len())
+        .map(|i| {
+            let (x, y) = (i as isize % width, i as isize / width);
+            let (dx, dy) = match direction_sector(gradients.direction[i]) {
+                0 => (1, 0),
+                1 => (1, 1),
+                2 => (0, 1),
+                _ => (-1, 1),
+            };
+            let m = magnitude[i];
+            // Strict on one side so a two-pixel plateau keeps only one pixel
+            if m >= at(x + dx, y + dy) && m > at(x - dx, y - dy) { m } else { 0.0 }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Luma;
+
+    fn step(vertical: bool) -> GrayImage {
+        GrayImage::from_fn(32, 32, |x, y| Luma([if (if vertical { x } else { y }) < 16 { 0 } else { 255 } ]))
+    }
+
+    /// Non-blank glyphs of each row
+    fn edge_rows(encoded: &str) -> Vec<Vec<(usize, char)>> {
+        encoded.lines()
+            .map(|line| line.chars().enumerate().filter(|&(_, c)| c != ' ').collect())
+            .collect()
+    }
+
+    #[test]
+    fn vertical_step_is_a_thin_line_of_bars() {
+        let rows = edge_rows(&encode_edges(&step(true), 32, 32, EdgeOptions::default()));
+        assert_eq!(rows.len(), 32);
+        let column = rows[0][0].0;
+        for row in &rows {
+            assert_eq!(row, &[(column, '|')], "one pixel wide after suppression");
+        }
+
+        // Without suppression the blurred step is several pixels wide
+        let thick = edge_rows(&encode_edges(&step(true), 32, 32, EdgeOptions { blur_sigma: Some(0.8), non_max_suppression: false }));
+        assert!(thick[16].len() > 1);
+    }
+
+    #[test]
+    fn horizontal_step_gives_dashes() {
+        let rows = edge_rows(&encode_edges(&step(false), 32, 32, EdgeOptions::default()));
+        let edge_rows: Vec<&Vec<(usize, char)>> = rows.iter().filter(|row| !row.is_empty()).collect();
+        assert_eq!(edge_rows.len(), 1);
+        assert!(edge_rows[0].len() == 32 && edge_rows[0].iter().all(|&(_, c)| c == '-'));
+    }
+
+    #[test]
+    fn flat_image_has_no_edges() {
+        let flat = GrayImage::from_pixel(32, 32, Luma([90]));
+        let encoded = encode_edges(&flat, 20, 10, EdgeOptions::default());
+        assert_eq!(encoded.lines().count(), 10);
+        assert!(encoded.chars().all(|c| c == ' ' || c == '\n'));
+
+        assert!(encode_edges(&flat, 0, 10, EdgeOptions::default()).is_empty());
+        assert!(encode_edges(&GrayImage::new(0, 0), 20, 10, EdgeOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn sobel_and_blur_on_a_step() {
+        let pixels: Vec<f32> = (0..64).map(|i| if i % 8 < 4 { 0.0 } else { 100.0 }).collect();
+        let gradients = sobel(&pixels, 8, 8);
+        // Full response on both sides of the step, pointing along +x, nothing elsewhere
+        assert_eq!(gradients.magnitude[3], 400.0);
+        assert_eq!(gradients.magnitude[4], 400.0);
+        assert_eq!(gradients.direction[3], 0.0);
+        assert_eq!(gradients.magnitude[0], 0.0);
+
+        let thinned = non_max_suppression(&gradients);
+        assert!((0..8).all(|y| thinned[y * 8 + 3] == 400.0 && thinned[y * 8 + 4] == 0.0));
+
+        let blurred = gaussian_blur(&pixels, 8, 8, 1.0);
+        assert!(blurred[3] > 0.0 && blurred[4] < 100.0);
+        assert!((blurred.iter().sum::<f32>() - pixels.iter().sum::<f32>()).abs() < 1e-2);
+        assert_eq!(gaussian_blur(&pixels, 8, 8, 0.0), pixels);
+    }
+}
@@ -1,12 +1,37 @@
 use crate::math::C;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use crate::hashing::{CoordHasher, SipHash13};
+use serde::{Serialize, Deserialize};
 
 pub type Fingerprint = Vec<u64>; // 32 u64s = 2048 bits
 
-#[derive(Clone, Copy, Debug, bincode::Encode, bincode::Decode)]
+/// Serde adapter writing a Fingerprint as one hex string (16 digits per word)
+pub mod fingerprint_hex {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::de::Error;
+    use super::Fingerprint;
+
+    pub fn serialize<S: Serializer>(fingerprint: &Fingerprint, serializer: S) -> Result<S::Ok, S::Error> {
+        let hex: String = fingerprint.iter().map(|word| format!("{:016x}", word)).collect();
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fingerprint, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if hex.len() % 16 != 0 || !hex.is_ascii() {
+            return Err(D::Error::custom("fingerprint hex must be 16 ascii digits per word"));
+        }
+        (0..hex.len())
+            .step_by(16)
+            .map(|i| u64::from_str_radix(&hex[i..i + 16], 16).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, bincode::Encode, bincode::Decode, Serialize, Deserialize)]
 pub struct MandelbrotCoord {
+    #[serde(with = "crate::persist::json_float")]
     pub re: f64,
+    #[serde(with = "crate::persist::json_float")]
     pub im: f64,
 }
 
@@ -39,10 +64,68 @@ pub fn julia_escapes(z0: C, c: C, max_iter: u32) -> u32 {
 }
 
 pub fn hash_to_mandelbrot_coord(input: &str) -> MandelbrotCoord {
-    let mut hasher = DefaultHasher::new();
-    input.hash(&mut hasher);
-    let hash = hasher.finish();
+    hash_to_mandelbrot_coord_with(&SipHash13, input)
+}
+
+pub fn hash_to_mandelbrot_coord_with<H: CoordHasher + ?Sized>(hasher: &H, input: &str) -> MandelbrotCoord {
+    hash_bits_to_mandelbrot_coord(hasher.hash_str(input))
+}
+
+/// Like hash_to_mandelbrot_coord_with, but keeps drawing from the input's hash stream until
+/// the point is in the boundary band (see in_boundary_band). Fingerprints are only varied
+/// there: points well outside the set escape within the first bin at mind scale, and points
+/// inside it never escape, so both merge with each other regardless of input. The band
+/// follows the whole boundary, so the points stay spread over the region.
+/// The first draw is used when none of MAX_BOUNDARY_DRAWS lands in the band.
+pub fn boundary_mandelbrot_coord_with<H: CoordHasher + ?Sized>(hasher: &H, input: &str) -> MandelbrotCoord {
+    let first = hasher.hash_str(input);
+    let mut hash = first;
+    for _ in 0..MAX_BOUNDARY_DRAWS {
+        let coord = hash_bits_to_mandelbrot_coord(hash);
+        if in_boundary_band(coord) {
+            return coord;
+        }
+        hash = splitmix64(hash);
+    }
+    hash_bits_to_mandelbrot_coord(first)
+}
+
+/// About 1 draw in 1500 lands in the band, so this runs out once in ~50000 inputs
+const MAX_BOUNDARY_DRAWS: usize = 16384;
+/// Scale the band is measured at - the default mind scale. Fixed rather than the mind's own
+/// scale: like the hasher, text coordinates must not move when the scale is changed. At
+/// another scale the band no longer matches the fingerprint bins as closely.
+const BOUNDARY_SCALE: u32 = 4096;
+/// Stability band kept, the one find_nearby_interesting_points samples
+const BOUNDARY_BAND: (f64, f64) = (0.3, 0.95);
+
+/// Outside the set, but escaping only after 30-95% of the default mind scale's iterations:
+/// the thin band along the set's boundary where Julia fingerprints spread over every bin
+pub fn in_boundary_band(coord: MandelbrotCoord) -> bool {
+    // The main cardioid never escapes - skip iterating it
+    if in_main_cardioid(coord) {
+        return false;
+    }
+    let stability = mandelbrot_stability(coord, BOUNDARY_SCALE);
+    stability > BOUNDARY_BAND.0 && stability < BOUNDARY_BAND.1
+}
 
+/// Closed-form test for the main cardioid
+fn in_main_cardioid(coord: MandelbrotCoord) -> bool {
+    let x = coord.re - 0.25;
+    let q = x * x + coord.im * coord.im;
+    q * (q + x) <= 0.25 * coord.im * coord.im
+}
+
+/// Next value of a hash stream (SplitMix64 step)
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn hash_bits_to_mandelbrot_coord(hash: u64) -> MandelbrotCoord {
     let re_bits = (hash >> 32) as u32;
     let im_bits = hash as u32;
 
@@ -55,9 +138,27 @@ pub fn hash_to_mandelbrot_coord(input: &str) -> MandelbrotCoord {
     MandelbrotCoord::new(re, im)
 }
 
+/// Squash an unbounded point smoothly into the region hash_to_mandelbrot_coord maps onto
+/// (tanh per axis, 0 at the region's center), so nearby points stay nearby
+pub fn plane_to_mandelbrot_coord(x: f64, y: f64) -> MandelbrotCoord {
+    MandelbrotCoord::new(-0.25 + 0.5 * x.tanh(), y.tanh())
+}
+
 pub fn contextual_mandelbrot_coord(base: MandelbrotCoord, context: &str, influence: f64) -> MandelbrotCoord {
-    let context_coord = hash_to_mandelbrot_coord(context);
+    contextual_mandelbrot_coord_with(&SipHash13, base, context, influence)
+}
+
+pub fn contextual_mandelbrot_coord_with<H: CoordHasher + ?Sized>(
+    hasher: &H,
+    base: MandelbrotCoord,
+    context: &str,
+    influence: f64,
+) -> MandelbrotCoord {
+    contextual_mandelbrot_coord_from(base, hash_to_mandelbrot_coord_with(hasher, context), influence)
+}
 
+/// Pull base a little toward where the context landed (however it was mapped)
+pub fn contextual_mandelbrot_coord_from(base: MandelbrotCoord, context_coord: MandelbrotCoord, influence: f64) -> MandelbrotCoord {
     let re = base.re + (context_coord.re - base.re) * influence * 0.1;
     let im = base.im + (context_coord.im - base.im) * influence * 0.1;
 
@@ -0,0 +1,172 @@
+// Frame sources for CameraEncoder
+// Anything that yields RGB24 frames - a V4L2 device, image files, raw frames, a generator -
+// so the camera path can run without a webcam.
+
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// One RGB24 frame, row-major, 3 bytes per pixel
+#[derive(Clone, Debug)]
+pub struct Frame {
+    pub data: Vec<u8>,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// Yields RGB24 frames
+pub trait FrameSource: Send {
+    fn next_frame(&mut self) -> Result<Frame, Box<dyn std::error::Error>>;
+}
+
+/// Back-to-back raw RGB24 frames of a fixed size, as written by
+/// `ffmpeg -i input -f rawvideo -pix_fmt rgb24 frames.rgb`
+pub struct RawRgbFile {
+    reader: BufReader<File>,
+    width: usize,
+    height: usize,
+    looping: bool,   // Rewind at end of file instead of failing, like a loopback device
+}
+
+impl RawRgbFile {
+    pub fn open<P: AsRef<Path>>(path: P, width: usize, height: usize, looping: bool) -> Result<Self, Box<dyn std::error::Error>> {
+        if width == 0 || height == 0 {
+            return Err("raw frame size must be non-zero".into());
+        }
+
+        let file = File::open(path)?;
+        if (file.metadata()?.len() as usize) < width * height * 3 {
+            return Err(format!("file holds less than one {}x{} RGB24 frame", width, height).into());
+        }
+
+        Ok(RawRgbFile { reader: BufReader::new(file), width, height, looping })
+    }
+}
+
+impl FrameSource for RawRgbFile {
+    fn next_frame(&mut self) -> Result<Frame, Box<dyn std::error::Error>> {
+        let mut data = vec![0u8; self.width * self.height * 3];
+
+        match self.reader.read_exact(&mut data) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                if !self.looping {
+                    return Err("end of raw frame file".into());
+                }
+                // A trailing partial frame is dropped
+                self.reader.seek(SeekFrom::Start(0))?;
+                self.reader.read_exact(&mut data)?;
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        Ok(Frame { data, width: self.width, height: self.height })
+    }
+}
+
+/// A directory of PNG/JPEG frames in sorted file name order (frame_0001.png, frame_0002.png, ...)
+pub struct ImageDirectory {
+    paths: Vec<PathBuf>,
+    next: usize,
+    looping: bool,
+}
+
+impl ImageDirectory {
+    pub fn open<P: AsRef<Path>>(dir: P, looping: bool) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir.as_ref())?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && is_frame_image(p))
+            .collect();
+        paths.sort();
+
+        if paths.is_empty() {
+            return Err(format!("no PNG/JPEG frames in {}", dir.as_ref().display()).into());
+        }
+
+        Ok(ImageDirectory { paths, next: 0, looping })
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+impl FrameSource for ImageDirectory {
+    fn next_frame(&mut self) -> Result<Frame, Box<dyn std::error::Error>> {
+        if self.next >= self.paths.len() {
+            if !self.looping {
+                return Err("end of frame directory".into());
+            }
+            self.next = 0;
+        }
+
+        let path = &self.paths[self.next];
+        self.next += 1;
+
+        let img = image::open(path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+            .to_rgb8();
+        let (width, height) = (img.width() as usize, img.height() as usize);
+
+        Ok(Frame { data: img.into_raw(), width, height })
+    }
+}
+
+fn is_frame_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| matches!(e.to_ascii_lowercase().as_str(), "png" | "jpg" | "jpeg"))
+        .unwrap_or(false)
+}
+
+/// Procedural test pattern: scrolling vertical bars with a square bouncing across them.
+/// Deterministic - frame n is always the same picture - and never runs out.
+pub struct SyntheticSource {
+    width: usize,
+    height: usize,
+    frame: usize,
+}
+
+impl SyntheticSource {
+    pub fn new(width: usize, height: usize) -> Self {
+        SyntheticSource { width: width.max(1), height: height.max(1), frame: 0 }
+    }
+}
+
+impl FrameSource for SyntheticSource {
+    fn next_frame(&mut self) -> Result<Frame, Box<dyn std::error::Error>> {
+        let (w, h, t) = (self.width, self.height, self.frame);
+        self.frame += 1;
+
+        // Square bounces between the edges, 3px/frame horizontally and 2px/frame vertically
+        let size = (w.min(h) / 4).max(1);
+        let bounce = |pos: usize, span: usize| {
+            if span == 0 { return 0; }
+            let p = pos % (2 * span);
+            if p < span { p } else { 2 * span - p }
+        };
+        let square_x = bounce(t * 3, w - size);
+        let square_y = bounce(t * 2, h - size);
+
+        let mut data = Vec::with_capacity(w * h * 3);
+        for y in 0..h {
+            for x in 0..w {
+                let in_square = (square_x..square_x + size).contains(&x) && (square_y..square_y + size).contains(&y);
+                if in_square {
+                    data.extend_from_slice(&[255, 255, 255]);
+                } else {
+                    let bar = ((x + t * 2) / 16) % 2 == 0;
+                    let shade = (y * 255 / h) as u8;
+                    data.extend_from_slice(&[if bar { 200 } else { 40 }, shade, 255 - shade]);
+                }
+            }
+        }
+
+        Ok(Frame { data, width: w, height: h })
+    }
+}
@@ -0,0 +1,119 @@
+// Multi-index hashing over Julia fingerprints
+// Exact search for "first symbol within Hamming distance < threshold" without scanning every pattern
+//
+// Fingerprints are split into `threshold` disjoint word blocks. If two fingerprints differ in
+// fewer than `threshold` bits, at least one block must match exactly (pigeonhole), so only
+// symbols sharing a block with the query need a full hamming_distance check.
+//
+// Blocks are strided (every n-th word) rather than contiguous: the outer rows of the Julia grid
+// escape immediately for almost every c, so contiguous edge blocks would match everything.
+
+use std::collections::HashMap;
+use bincode::{Encode, Decode};
+use bincode::enc::Encoder;
+use bincode::de::Decoder;
+use bincode::error::{EncodeError, DecodeError};
+
+use crate::fractal::{Fingerprint, hamming_distance};
+use crate::memory::FractalSymbol;
+
+const FINGERPRINT_WORDS: usize = 32;  // 2048 bits, see julia_fingerprint_from_mandelbrot
+
+/// Exact Hamming index over FractalSymbol patterns.
+/// Derived data - never persisted, rebuilt from the symbols after loading or pruning.
+pub struct HammingIndex {
+    block_count: usize,
+    tables: Vec<HashMap<u64, Vec<usize>>>,  // block key -> symbol indices (ascending)
+}
+
+impl HammingIndex {
+    /// Index that answers distance < `threshold` queries exactly.
+    /// Thresholds above 32 can't be split into word blocks and fall back to a linear scan.
+    pub fn new(threshold: u32) -> Self {
+        let block_count = if (threshold as usize) <= FINGERPRINT_WORDS { threshold as usize } else { 0 };
+
+        HammingIndex {
+            block_count,
+            tables: vec![HashMap::new(); block_count],
+        }
+    }
+
+    pub fn rebuild(&mut self, symbols: &[FractalSymbol]) {
+        for table in &mut self.tables {
+            table.clear();
+        }
+        for (symbol_idx, symbol) in symbols.iter().enumerate() {
+            self.insert(symbol_idx, &symbol.pattern);
+        }
+    }
+
+    pub fn insert(&mut self, symbol_idx: usize, pattern: &Fingerprint) {
+        // Symbols are inserted in index order, so every bucket stays sorted
+        for (block, table) in self.tables.iter_mut().enumerate() {
+            table.entry(block_key(pattern, block, self.block_count)).or_default().push(symbol_idx);
+        }
+    }
+
+    /// Lowest-indexed symbol whose pattern is within distance < `threshold` of `pattern` -
+    /// the same symbol a front-to-back linear scan would merge into
+    pub fn first_within(&self, symbols: &[FractalSymbol], pattern: &Fingerprint, threshold: u32) -> Option<usize> {
+        if threshold as usize > self.block_count {
+            // Pigeonhole guarantee doesn't hold for this threshold
+            return linear_first_within(symbols, pattern, threshold);
+        }
+
+        let mut best: Option<usize> = None;
+
+        for (block, table) in self.tables.iter().enumerate() {
+            // Key collisions only add candidates - every one is verified below
+            let Some(bucket) = table.get(&block_key(pattern, block, self.block_count)) else {
+                continue;
+            };
+            for &symbol_idx in bucket {
+                if best.is_some_and(|b| symbol_idx >= b) {
+                    break;
+                }
+                if hamming_distance(pattern, &symbols[symbol_idx].pattern) < threshold {
+                    best = Some(symbol_idx);
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl Default for HammingIndex {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// Reference linear scan - what store_symbol_at_coord used to do
+pub fn linear_first_within(symbols: &[FractalSymbol], pattern: &Fingerprint, threshold: u32) -> Option<usize> {
+    symbols.iter().position(|symbol| hamming_distance(pattern, &symbol.pattern) < threshold)
+}
+
+// Mix words block, block + block_count, block + 2*block_count, ... into one key
+fn block_key(pattern: &Fingerprint, block: usize, block_count: usize) -> u64 {
+    pattern.iter()
+        .skip(block)
+        .step_by(block_count)
+        .fold(0xcbf29ce484222325, |key, &word| (key ^ word).wrapping_mul(0x100000001b3).rotate_left(31))
+}
+
+// Derived state: encodes to nothing, decodes empty - FractalMind::load rebuilds it
+impl Encode for HammingIndex {
+    fn encode<E: Encoder>(&self, _encoder: &mut E) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<Context> Decode<Context> for HammingIndex {
+    fn decode<D: Decoder<Context = Context>>(_decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(HammingIndex::default())
+    }
+}
+
+bincode::impl_borrow_decode!(HammingIndex);
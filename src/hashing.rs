@@ -0,0 +1,143 @@
+// String hashing for concept coordinates
+// std's DefaultHasher is explicitly allowed to change between Rust releases, which would
+// relocate every concept in a saved mind - these implementations are pinned forever.
+
+use bincode::{Encode, Decode};
+use serde::{Serialize, Deserialize};
+
+/// Hashes a concept string to the 64 bits hash_to_mandelbrot_coord splits into re/im
+pub trait CoordHasher {
+    fn hash_str(&self, input: &str) -> u64;
+}
+
+/// SipHash-1-3 with zero keys over the UTF-8 bytes plus a 0xFF terminator.
+/// Bit-for-bit what `DefaultHasher::new()` + `str::hash` produced when minds were first
+/// trained, so existing coordinates stay where they are.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SipHash13;
+
+/// 64-bit FNV-1a over the UTF-8 bytes - simple to reimplement in other languages
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Fnv1a;
+
+/// Which hasher a mind's coordinates were built with - saved with the mind
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Encode, Decode, Serialize, Deserialize)]
+pub enum CoordHasherKind {
+    #[default]
+    SipHash13,
+    Fnv1a,
+}
+
+impl CoordHasher for CoordHasherKind {
+    fn hash_str(&self, input: &str) -> u64 {
+        match self {
+            CoordHasherKind::SipHash13 => SipHash13.hash_str(input),
+            CoordHasherKind::Fnv1a => Fnv1a.hash_str(input),
+        }
+    }
+}
+
+impl CoordHasher for SipHash13 {
+    fn hash_str(&self, input: &str) -> u64 {
+        let mut message = Vec::with_capacity(input.len() + 1);
+        message.extend_from_slice(input.as_bytes());
+        message.push(0xff);
+        siphash13(0, 0, &message)
+    }
+}
+
+impl CoordHasher for Fnv1a {
+    fn hash_str(&self, input: &str) -> u64 {
+        input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+    }
+}
+
+fn siphash13(k0: u64, k1: u64, message: &[u8]) -> u64 {
+    let mut v = [
+        k0 ^ 0x736f6d6570736575,
+        k1 ^ 0x646f72616e646f6d,
+        k0 ^ 0x6c7967656e657261,
+        k1 ^ 0x7465646279746573,
+    ];
+
+    let mut blocks = message.chunks_exact(8);
+    for block in &mut blocks {
+        let m = u64::from_le_bytes(block.try_into().unwrap());
+        v[3] ^= m;
+        sip_round(&mut v);
+        v[0] ^= m;
+    }
+
+    let mut last = (message.len() as u64 & 0xff) << 56;
+    for (i, &byte) in blocks.remainder().iter().enumerate() {
+        last |= (byte as u64) << (8 * i);
+    }
+    v[3] ^= last;
+    sip_round(&mut v);
+    v[0] ^= last;
+
+    v[2] ^= 0xff;
+    for _ in 0..3 {
+        sip_round(&mut v);
+    }
+
+    v[0] ^ v[1] ^ v[2] ^ v[3]
+}
+
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractal::hash_to_mandelbrot_coord_with;
+
+    // (input, hash, re, im) - these pin where concepts in saved minds live, never update them
+    const SIPHASH13_GOLDEN: [(&str, u64, f64, f64); 5] = [
+        ("", 0x30406ea523c53def, -0.5615168425281338, -0.7205431539845986),
+        ("a", 0x719b50b9a4f0e9f3, -0.3062238261467367, 0.28860210797018415),
+        ("hello", 0xe037876b880b8ed9, 0.12584730514228515, 0.06285272237445527),
+        ("the cat sat", 0x97ab2e6bf0d65def, -0.15754423020583208, 0.8815419599138066),
+        ("日本語", 0x45b6f3c35fcdca3e, -0.47767712099656395, -0.25153228716261966),
+    ];
+
+    const FNV1A_GOLDEN: [(&str, u64, f64, f64); 5] = [
+        ("", 0xcbf29ce484222325, 0.046670728548586116, 0.03229178745120098),
+        ("a", 0xaf63dc4c8601ec8c, -0.0648824990063166, 0.046933716406797554),
+        ("hello", 0xa430d84680aabd0b, -0.1086296870742528, 0.005210524193293153),
+        ("the cat sat", 0xa02552c16cdea15c, -0.12443049214184998, -0.1494558619217612),
+        ("日本語", 0xee9ee2b5c854ef87, 0.18211190168096492, 0.5650920308113778),
+    ];
+
+    fn assert_golden<H: CoordHasher>(hasher: &H, golden: &[(&str, u64, f64, f64)]) {
+        for &(input, hash, re, im) in golden {
+            assert_eq!(hasher.hash_str(input), hash, "hash of {:?}", input);
+            let coord = hash_to_mandelbrot_coord_with(hasher, input);
+            assert_eq!((coord.re, coord.im), (re, im), "coordinate of {:?}", input);
+        }
+    }
+
+    #[test]
+    fn siphash13_is_pinned() {
+        assert_golden(&SipHash13, &SIPHASH13_GOLDEN);
+        assert_golden(&CoordHasherKind::SipHash13, &SIPHASH13_GOLDEN);
+    }
+
+    #[test]
+    fn fnv1a_is_pinned() {
+        assert_golden(&Fnv1a, &FNV1A_GOLDEN);
+        assert_golden(&CoordHasherKind::Fnv1a, &FNV1A_GOLDEN);
+    }
+}
@@ -4,25 +4,75 @@
 use image::{DynamicImage, GenericImageView, imageops};
 use std::path::Path;
 
+use crate::color::{srgb_to_lab, lab_to_srgb, rgb_to_hsv, kmeans_lab};
+use crate::edges::{encode_edges, EdgeOptions};
+use crate::texture::{cell_textures, CellTexture};
+use crate::sensory::SensoryVector;
+
+/// Pixels sampled for dominant color clustering
+const MAX_COLOR_SAMPLES: u32 = 4096;
+
 /// Encoding strategy for images/video frames
 #[derive(Clone, Copy)]
 pub enum ImageEncoding {
     /// Downsampled pixel grid (similar to camera encoding)
     PixelGrid { width: usize, height: usize },
 
-    /// Dominant colors extracted (e.g., "rgb:120,45,200|rgb:180,90,50|...")
+    /// Dominant colors by k-means in CIELAB with percent weights, dark to light
+    /// (e.g., "colors:rgb:32,48,96@45|rgb:176,96,48@30|...")
     DominantColors { color_count: usize },
 
-    /// Edge detection ASCII art
+    /// Sobel edges as orientation glyphs, one line per row (e.g., " .-- \n |  /\n")
     EdgeAscii { width: usize, height: usize },
 
-    /// Histogram-based encoding (frequency distribution)
-    ColorHistogram { bins: usize },
+    /// Normalized color histogram, each bin's share quantized to 0..levels-1 on a square-root
+    /// scale, so the same picture encodes alike at any resolution.
+    /// Rgb: one histogram per channel with bins[0], bins[1], bins[2] bins (e.g., "hist:rgb|0,4,2,0|1,3,2,0|0,0,5,3").
+    /// Hsv: one joint hue x saturation x value histogram, grouped by hue (e.g., "hist:hsv|0,3,1,0|...").
+    ColorHistogram { space: HistogramSpace, bins: [usize; 3], levels: usize },
 
-    /// Spatial frequency encoding (texture patterns)
+    /// Local binary pattern and Gabor texture symbols for a grid_size x grid_size grid
+    /// (e.g., "texture:2|f4,0of8,3of9,..." - see texture::CellTexture::symbol)
     TexturePattern { grid_size: usize },
 }
 
+/// Color space of ImageEncoding::ColorHistogram
+#[derive(Clone, Copy, Debug)]
+pub enum HistogramSpace {
+    Rgb,
+    Hsv,
+}
+
+impl ImageEncoding {
+    /// Short name of the channel this encoding fills in a multi-channel encoding
+    pub fn channel_name(&self) -> &'static str {
+        match self {
+            ImageEncoding::PixelGrid { .. } => "pixels",
+            ImageEncoding::DominantColors { .. } => "colors",
+            ImageEncoding::EdgeAscii { .. } => "edges",
+            ImageEncoding::ColorHistogram { .. } => "histogram",
+            ImageEncoding::TexturePattern { .. } => "texture",
+        }
+    }
+
+    /// Shape, color, color distribution and texture - what /image, /images and /learn learn
+    pub fn default_channels() -> Vec<ImageEncoding> {
+        vec![
+            ImageEncoding::EdgeAscii { width: 40, height: 40 },
+            ImageEncoding::DominantColors { color_count: 5 },
+            ImageEncoding::ColorHistogram { space: HistogramSpace::Hsv, bins: [8, 3, 3], levels: 8 },
+            ImageEncoding::TexturePattern { grid_size: 4 },
+        ]
+    }
+}
+
+/// One channel of a multi-channel image encoding
+#[derive(Clone, Debug)]
+pub struct ImageChannel {
+    pub name: &'static str,
+    pub encoded: String,
+}
+
 /// Load and encode an image file
 pub fn encode_image<P: AsRef<Path>>(
     path: P,
@@ -32,6 +82,24 @@ pub fn encode_image<P: AsRef<Path>>(
     Ok(encode_image_data(&img, encoding))
 }
 
+/// Load an image file once and encode it with every given strategy, one channel each
+pub fn encode_image_multi<P: AsRef<Path>>(
+    path: P,
+    encodings: &[ImageEncoding],
+) -> Result<Vec<ImageChannel>, Box<dyn std::error::Error>> {
+    let img = image::open(path)?;
+    Ok(encode_image_data_multi(&img, encodings))
+}
+
+pub fn encode_image_data_multi(img: &DynamicImage, encodings: &[ImageEncoding]) -> Vec<ImageChannel> {
+    encodings.iter()
+        .map(|&encoding| ImageChannel {
+            name: encoding.channel_name(),
+            encoded: encode_image_data(img, encoding),
+        })
+        .collect()
+}
+
 /// Encode image data using specified strategy
 pub fn encode_image_data(img: &DynamicImage, encoding: ImageEncoding) -> String {
     match encoding {
@@ -42,10 +110,10 @@ pub fn encode_image_data(img: &DynamicImage, encoding: ImageEncoding) -> String
             encode_dominant_colors(img, color_count)
         }
         ImageEncoding::EdgeAscii { width, height } => {
-            encode_edge_ascii(img, width, height)
+            encode_edges(&img.to_luma8(), width, height, EdgeOptions::default())
         }
-        ImageEncoding::ColorHistogram { bins } => {
-            encode_color_histogram(img, bins)
+        ImageEncoding::ColorHistogram { space, bins, levels } => {
+            encode_color_histogram(img, space, bins, levels)
         }
         ImageEncoding::TexturePattern { grid_size } => {
             encode_texture_pattern(img, grid_size)
@@ -53,6 +121,91 @@ pub fn encode_image_data(img: &DynamicImage, encoding: ImageEncoding) -> String
     }
 }
 
+/// Load an image file as a pixel grid vector (see pixel_grid_vector)
+pub fn image_vector<P: AsRef<Path>>(
+    path: P,
+    width: usize,
+    height: usize,
+) -> Result<SensoryVector, Box<dyn std::error::Error>> {
+    let img = image::open(path)?;
+    Ok(pixel_grid_vector(&img, width, height))
+}
+
+/// Downsample image to grid as numbers: R, G, B in 0..1 for each pixel, row by row.
+/// Filtered rather than sampled, so a slightly shifted or rescaled picture gives a close vector.
+pub fn pixel_grid_vector(img: &DynamicImage, target_w: usize, target_h: usize) -> SensoryVector {
+    if target_w == 0 || target_h == 0 || img.width() == 0 || img.height() == 0 {
+        return SensoryVector::new("pixels", Vec::new());
+    }
+
+    let resized = img.resize_exact(target_w as u32, target_h as u32, imageops::FilterType::Triangle);
+    let values = resized.to_rgb8().pixels()
+        .flat_map(|p| p.0)
+        .map(|c| c as f32 / 255.0)
+        .collect();
+    SensoryVector::new("pixels", values)
+}
+
+/// The numeric form of a `colors:` or `hist:` channel string, so the channel alone can be
+/// placed by similarity (see FractalMind::recall_images_by_channel). Colors become their
+/// weighted closeness to each color of a coarse palette, histograms their bin levels scaled
+/// to 0..1. None for other channels.
+pub fn channel_vector(encoded: &str) -> Option<SensoryVector> {
+    if let Some(colors) = encoded.strip_prefix("colors:") {
+        let colors: Vec<([f32; 3], f32)> = colors.split('|')
+            .filter(|entry| !entry.is_empty())
+            .map(parse_color_entry)
+            .collect::<Option<_>>()?;
+        if colors.is_empty() {
+            return None;
+        }
+
+        let values = palette_lab().iter()
+            .map(|&entry| {
+                colors.iter()
+                    .map(|&(lab, weight)| {
+                        let distance_sq: f32 = lab.iter().zip(entry).map(|(a, b)| (a - b) * (a - b)).sum();
+                        weight * (-distance_sq / (2.0 * PALETTE_SIGMA * PALETTE_SIGMA)).exp()
+                    })
+                    .sum()
+            })
+            .collect();
+        return Some(SensoryVector::new("colors", values));
+    }
+
+    if let Some(histogram) = encoded.strip_prefix("hist:") {
+        let (space, bins) = histogram.split_once('|')?;
+        let levels: Vec<f32> = bins.split(['|', ','])
+            .map(|level| level.parse().ok())
+            .collect::<Option<_>>()?;
+        let top = levels.iter().copied().fold(1.0, f32::max);
+        let modality = if space == "rgb" { "histogram:rgb" } else { "histogram:hsv" };
+        return Some(SensoryVector::new(modality, levels.iter().map(|level| level / top).collect()));
+    }
+
+    None
+}
+
+/// Palette channel_vector measures colors against: every mix of these per RGB channel
+const PALETTE_LEVELS: [u8; 3] = [0, 128, 255];
+/// CIELAB distance at which a color counts ~60% toward a palette color
+const PALETTE_SIGMA: f32 = 30.0;
+
+fn palette_lab() -> Vec<[f32; 3]> {
+    PALETTE_LEVELS.iter()
+        .flat_map(|&r| PALETTE_LEVELS.iter().flat_map(move |&g| PALETTE_LEVELS.iter().map(move |&b| [r, g, b])))
+        .map(srgb_to_lab)
+        .collect()
+}
+
+/// "rgb:224,32,32@69" -> (its Lab color, 0.69)
+fn parse_color_entry(entry: &str) -> Option<([f32; 3], f32)> {
+    let (rgb, percent) = entry.strip_prefix("rgb:")?.split_once('@')?;
+    let mut channels = rgb.split(',').map(|c| c.parse::<u8>().ok());
+    let color = [channels.next()??, channels.next()??, channels.next()??];
+    Some((srgb_to_lab(color), percent.parse::<f32>().ok()? / 100.0))
+}
+
 /// Downsample image to grid and encode as comma-separated RGB values
 fn encode_pixel_grid_image(img: &DynamicImage, target_w: usize, target_h: usize) -> String {
     let resized = img.resize_exact(target_w as u32, target_h as u32, imageops::FilterType::Nearest);
@@ -67,199 +220,195 @@ fn encode_pixel_grid_image(img: &DynamicImage, target_w: usize, target_h: usize)
     output
 }
 
-/// Extract dominant colors using simple k-means-like clustering
+/// Extract dominant colors with k-means in CIELAB over an evenly spaced pixel sample.
+/// Colors are snapped to a 16-level grid, weights to whole percent, and listed dark to light
+/// so near-identical images encode identically.
 fn encode_dominant_colors(img: &DynamicImage, color_count: usize) -> String {
     let rgb = img.to_rgb8();
     let (width, height) = img.dimensions();
 
-    // Sample pixels uniformly
-    let sample_step = ((width * height) / 1000).max(1);
+    // Same spacing on both axes, giving about MAX_COLOR_SAMPLES samples at any image size
+    let step = ((width as f64 * height as f64 / MAX_COLOR_SAMPLES as f64).sqrt().ceil() as u32).max(1);
     let mut samples = Vec::new();
 
-    for y in (0..height).step_by(sample_step as usize) {
-        for x in (0..width).step_by(sample_step as usize) {
-            let pixel = rgb.get_pixel(x, y);
-            samples.push([pixel[0] as u32, pixel[1] as u32, pixel[2] as u32]);
+    for y in (step / 2..height).step_by(step as usize) {
+        for x in (step / 2..width).step_by(step as usize) {
+            samples.push(srgb_to_lab(rgb.get_pixel(x, y).0));
         }
     }
 
-    if samples.is_empty() {
+    if samples.is_empty() || color_count == 0 {
         return "colors:none".to_string();
     }
 
-    // Simple clustering: divide color space into regions
-    let mut clusters = Vec::new();
-    let step = 256 / ((color_count as f32).cbrt().ceil() as u32);
-
-    for r in (0..256).step_by(step as usize) {
-        for g in (0..256).step_by(step as usize) {
-            for b in (0..256).step_by(step as usize) {
-                clusters.push(([r as u32, g as u32, b as u32], 0usize));
-            }
+    // Clusters that snap to the same color are merged
+    let mut colors: Vec<([u8; 3], f32)> = Vec::new();
+    for cluster in kmeans_lab(&samples, color_count) {
+        let snapped = lab_to_srgb(cluster.lab).map(|c| ((c as u32 + 8) / 16 * 16).min(255) as u8);
+        match colors.iter_mut().find(|(color, _)| *color == snapped) {
+            Some((_, weight)) => *weight += cluster.weight,
+            None => colors.push((snapped, cluster.weight)),
         }
     }
 
-    // Assign samples to nearest cluster
-    for sample in &samples {
-        let mut best_dist = u32::MAX;
-        let mut best_idx = 0;
-
-        for (i, (cluster, _)) in clusters.iter().enumerate() {
-            let dist = color_distance(*sample, *cluster);
-            if dist < best_dist {
-                best_dist = dist;
-                best_idx = i;
-            }
-        }
-
-        clusters[best_idx].1 += 1;
-    }
-
-    // Sort by frequency and take top N
-    clusters.sort_by(|a, b| b.1.cmp(&a.1));
+    colors.sort_by(|a, b| {
+        let (la, lb) = (srgb_to_lab(a.0), srgb_to_lab(b.0));
+        la[0].total_cmp(&lb[0]).then(a.0.cmp(&b.0))
+    });
 
     let mut output = String::from("colors:");
-    for i in 0..color_count.min(clusters.len()) {
-        let (color, _count) = clusters[i];
-        output.push_str(&format!("rgb:{},{},{}|", color[0], color[1], color[2]));
+    for (color, weight) in colors {
+        output.push_str(&format!("rgb:{},{},{}@{}|", color[0], color[1], color[2], (weight * 100.0).round()));
     }
 
     output
 }
 
-/// Simple Euclidean distance between RGB colors
-fn color_distance(a: [u32; 3], b: [u32; 3]) -> u32 {
-    let dr = (a[0] as i32 - b[0] as i32).pow(2);
-    let dg = (a[1] as i32 - b[1] as i32).pow(2);
-    let db = (a[2] as i32 - b[2] as i32).pow(2);
-    (dr + dg + db) as u32
-}
+/// Encode normalized RGB or HSV histograms. Zero bins or levels are treated as 1 and 2.
+fn encode_color_histogram(img: &DynamicImage, space: HistogramSpace, bins: [usize; 3], levels: usize) -> String {
+    let rgb = img.to_rgb8();
+    let bins = bins.map(|b| b.max(1));
+    let levels = levels.max(2);
 
-/// Edge detection using gradient, output as ASCII art
-fn encode_edge_ascii(img: &DynamicImage, target_w: usize, target_h: usize) -> String {
-    let resized = img.resize_exact(target_w as u32, target_h as u32, imageops::FilterType::Triangle);
-    let gray = resized.to_luma8();
-
-    let mut output = String::with_capacity(target_w * target_h + target_h);
-
-    for y in 0..target_h {
-        for x in 0..target_w {
-            let center = gray.get_pixel(x as u32, y as u32)[0] as i32;
-
-            let right = if x + 1 < target_w {
-                gray.get_pixel((x + 1) as u32, y as u32)[0] as i32
-            } else {
-                center
-            };
-
-            let down = if y + 1 < target_h {
-                gray.get_pixel(x as u32, (y + 1) as u32)[0] as i32
-            } else {
-                center
-            };
-
-            let gradient = (right - center).abs() + (down - center).abs();
-
-            let symbol = match gradient {
-                0..=20 => ' ',
-                21..=50 => '.',
-                51..=100 => '-',
-                101..=150 => '|',
-                _ => '#',
-            };
-            output.push(symbol);
-        }
-        output.push('\n');
+    let pixel_count = rgb.pixels().len();
+    if pixel_count == 0 {
+        return "hist:empty".to_string();
     }
 
-    output
+    // Value in 0..1 to its bin
+    let bin_of = |value: f32, count: usize| ((value * count as f32) as usize).min(count - 1);
+    let quantize = |count: u32| ((count as f32 / pixel_count as f32).sqrt() * (levels - 1) as f32).round() as u32;
+    let join = |counts: &[u32]| counts.iter().map(|&c| quantize(c).to_string()).collect::<Vec<_>>().join(",");
+
+    match space {
+        HistogramSpace::Rgb => {
+            let mut histograms = bins.map(|b| vec![0u32; b]);
+            for pixel in rgb.pixels() {
+                for (channel, histogram) in histograms.iter_mut().enumerate() {
+                    let b = histogram.len();
+                    histogram[bin_of(pixel[channel] as f32 / 256.0, b)] += 1;
+                }
+            }
+
+            let channels: Vec<String> = histograms.iter().map(|h| join(h)).collect();
+            format!("hist:rgb|{}", channels.join("|"))
+        }
+        HistogramSpace::Hsv => {
+            let [hue_bins, sat_bins, val_bins] = bins;
+            let mut histogram = vec![0u32; hue_bins * sat_bins * val_bins];
+            for pixel in rgb.pixels() {
+                let [h, s, v] = rgb_to_hsv(pixel.0);
+                let index = (bin_of(h, hue_bins) * sat_bins + bin_of(s, sat_bins)) * val_bins + bin_of(v, val_bins);
+                histogram[index] += 1;
+            }
+
+            let hues: Vec<String> = histogram.chunks(sat_bins * val_bins).map(join).collect();
+            format!("hist:hsv|{}", hues.join("|"))
+        }
+    }
 }
 
-/// Encode color histogram (distribution of RGB values)
-fn encode_color_histogram(img: &DynamicImage, bins: usize) -> String {
-    let rgb = img.to_rgb8();
-    let mut histogram = vec![0u32; bins];
+/// Encode each grid cell's LBP and Gabor texture as a symbol, comma-separated row by row
+fn encode_texture_pattern(img: &DynamicImage, grid_size: usize) -> String {
+    let cells = cell_textures(&img.to_luma8(), grid_size);
+    let symbols: Vec<String> = cells.iter().map(CellTexture::symbol).collect();
+    format!("texture:{}", symbols.join(","))
+}
 
-    let bin_size = 256 / bins;
+/// Video file processing (reads frames sequentially)
+/// Animated GIF, APNG and Y4M decode natively; other containers need the `ffmpeg` feature.
+pub struct VideoEncoder;
 
-    for pixel in rgb.pixels() {
-        let luminance = (pixel[0] as u32 + pixel[1] as u32 + pixel[2] as u32) / 3;
-        let bin_idx = (luminance / bin_size as u32).min(bins as u32 - 1) as usize;
-        histogram[bin_idx] += 1;
+impl VideoEncoder {
+    /// Extract frames from video file and encode each.
+    /// After every encoded frame, `frame_skip` frames are dropped (0 = encode every frame).
+    pub fn encode_video_frames<P: AsRef<Path>>(
+        path: P,
+        encoding: ImageEncoding,
+        frame_skip: usize,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        crate::video::decode_frames(path)?
+            .step_by(frame_skip + 1)
+            .map(|frame| frame.map(|img| encode_image_data(&img, encoding)))
+            .collect()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::image::{Rgb, RgbImage};
+
+    const SPACES: [HistogramSpace; 2] = [HistogramSpace::Rgb, HistogramSpace::Hsv];
 
-    let mut output = String::from("hist:");
-    for count in histogram {
-        output.push_str(&format!("{},", count));
+    /// Four flat quadrants (red, green, blue, grey), `scale` pixels per quadrant side
+    fn quadrants(scale: u32) -> DynamicImage {
+        let colors = [[220, 30, 30], [30, 200, 60], [40, 60, 210], [128, 128, 128]];
+        DynamicImage::ImageRgb8(RgbImage::from_fn(2 * scale, 2 * scale, |x, y| {
+            Rgb(colors[(y / scale * 2 + x / scale) as usize])
+        }))
     }
 
-    output
-}
+    #[test]
+    fn histogram_of_empty_image() {
+        let empty = DynamicImage::ImageRgb8(RgbImage::new(0, 0));
+        for space in SPACES {
+            assert_eq!(encode_color_histogram(&empty, space, [4, 4, 4], 8), "hist:empty");
+        }
+    }
 
-/// Encode texture patterns by analyzing spatial frequency
-fn encode_texture_pattern(img: &DynamicImage, grid_size: usize) -> String {
-    let gray = img.to_luma8();
-    let (width, height) = gray.dimensions();
-
-    let cell_w = width / grid_size as u32;
-    let cell_h = height / grid_size as u32;
-
-    let mut output = String::from("texture:");
-
-    for gy in 0..grid_size {
-        for gx in 0..grid_size {
-            let x0 = gx as u32 * cell_w;
-            let y0 = gy as u32 * cell_h;
-            let x1 = ((gx + 1) as u32 * cell_w).min(width);
-            let y1 = ((gy + 1) as u32 * cell_h).min(height);
-
-            // Compute variance within cell (texture measure)
-            let mut sum = 0u32;
-            let mut sum_sq = 0u32;
-            let mut count = 0u32;
-
-            for y in y0..y1 {
-                for x in x0..x1 {
-                    let val = gray.get_pixel(x, y)[0] as u32;
-                    sum += val;
-                    sum_sq += val * val;
-                    count += 1;
-                }
-            }
+    #[test]
+    fn histogram_of_single_pixel() {
+        let pixel = DynamicImage::ImageRgb8(RgbImage::from_pixel(1, 1, Rgb([255, 0, 0])));
+        assert_eq!(encode_color_histogram(&pixel, HistogramSpace::Rgb, [2, 2, 2], 4), "hist:rgb|0,3|3,0|3,0");
+        // Pure red: hue 0, full saturation, full value - the last of the first hue's bins
+        assert_eq!(encode_color_histogram(&pixel, HistogramSpace::Hsv, [2, 2, 2], 4), "hist:hsv|0,0,0,3|0,0,0,0");
+    }
 
-            let mean = sum / count;
-            let variance = (sum_sq / count) - (mean * mean);
+    #[test]
+    fn histogram_with_zero_bins_or_levels() {
+        let img = quadrants(4);
+        for space in SPACES {
+            // Zero bins act as one bin holding everything
+            let one_bin = encode_color_histogram(&img, space, [1, 1, 1], 8);
+            assert_eq!(encode_color_histogram(&img, space, [0, 0, 0], 8), one_bin);
+
+            // Zero or one level act as two
+            let two_levels = encode_color_histogram(&img, space, [4, 3, 3], 2);
+            assert_eq!(encode_color_histogram(&img, space, [4, 3, 3], 0), two_levels);
+            assert_eq!(encode_color_histogram(&img, space, [4, 3, 3], 1), two_levels);
+        }
+        assert_eq!(encode_color_histogram(&img, HistogramSpace::Rgb, [0, 0, 0], 8), "hist:rgb|7|7|7");
+    }
 
-            let symbol = match variance {
-                0..=100 => '.',
-                101..=500 => '-',
-                501..=1500 => '|',
-                _ => '#',
-            };
-            output.push(symbol);
+    #[test]
+    fn histogram_ignores_resolution() {
+        for space in SPACES {
+            let encoding = ImageEncoding::ColorHistogram { space, bins: [8, 3, 3], levels: 8 };
+            let small = encode_image_data(&quadrants(1), encoding);
+            for scale in [2, 7, 32] {
+                assert_eq!(encode_image_data(&quadrants(scale), encoding), small, "{}x scale", scale);
+            }
         }
     }
 
-    output
-}
+    #[test]
+    fn video_frame_skip_keeps_every_other_frame() {
+        // Five 2x2 greyscale frames, each a different shade
+        let mut y4m = b"YUV4MPEG2 W2 H2 F25:1 Cmono\n".to_vec();
+        for shade in [16, 70, 125, 180, 235] {
+            y4m.extend(b"FRAME\n");
+            y4m.extend([shade; 4]);
+        }
+        let path = std::env::temp_dir().join(format!("fractal_frame_skip_{}.y4m", std::process::id()));
+        std::fs::write(&path, y4m).unwrap();
 
-/// Video file processing (reads frames sequentially)
-pub struct VideoEncoder {
-    // For now, this is a placeholder - full video decoding would require ffmpeg/gstreamer
-    // You can implement frame extraction using external tools or libraries
-}
+        let encoding = ImageEncoding::PixelGrid { width: 1, height: 1 };
+        let all = VideoEncoder::encode_video_frames(&path, encoding, 0).unwrap();
+        let skipped = VideoEncoder::encode_video_frames(&path, encoding, 1).unwrap();
+        std::fs::remove_file(&path).unwrap();
 
-impl VideoEncoder {
-    /// Extract frames from video file and encode each
-    /// Note: Requires external video decoding library (ffmpeg-next, gstreamer, etc.)
-    pub fn encode_video_frames<P: AsRef<Path>>(
-        _path: P,
-        _encoding: ImageEncoding,
-        _frame_skip: usize,
-    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        // Placeholder for video frame extraction
-        // Implementation would use ffmpeg-next or similar to decode video frames
-        // and encode each frame using encode_image_data()
-        Err("video decoding not yet implemented - use ffmpeg to extract frames first".into())
+        assert_eq!(all.len(), 5);
+        assert_eq!(skipped, vec![all[0].clone(), all[2].clone(), all[4].clone()]);
     }
 }
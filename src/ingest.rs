@@ -0,0 +1,466 @@
+// Text ingestion pipeline for /read
+// Walks configurable roots, chunks text without splitting UTF-8, reports progress and
+// keeps a manifest of ingested offsets so an interrupted run resumes where it stopped.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Instant, UNIX_EPOCH};
+use serde::{Serialize, Deserialize};
+
+use crate::persist::write_atomic;
+
+/// How files are cut into pieces for the mind
+#[derive(Clone, Copy, Debug)]
+pub enum Chunking {
+    /// Fixed byte budget, cut at the nearest character boundary
+    Bytes { max_bytes: usize },
+
+    /// Whole sentences packed up to max_bytes (long sentences are cut at a character boundary)
+    Sentences { max_bytes: usize },
+
+    /// Whole paragraphs (blank-line separated) packed up to max_bytes
+    Paragraphs { max_bytes: usize },
+}
+
+#[derive(Clone, Debug)]
+pub struct IngestConfig {
+    pub roots: Vec<PathBuf>,          // Walked in order, files sorted within each root
+    pub include: Vec<String>,         // File name globs (* and ?), empty = everything
+    pub chunking: Chunking,
+    pub manifest_path: PathBuf,
+    pub checkpoint_every: usize,      // Chunks between checkpoints (plus one after every file)
+}
+
+impl Default for IngestConfig {
+    fn default() -> Self {
+        IngestConfig {
+            // Curriculum order: data/00 (easiest) to data/07 (hardest)
+            roots: (0..8).map(|i| PathBuf::from(format!("data/{:02}", i))).collect(),
+            include: Vec::new(),
+            chunking: Chunking::Sentences { max_bytes: 10000 },
+            manifest_path: PathBuf::from("ingest_manifest.json"),
+            checkpoint_every: 50,
+        }
+    }
+}
+
+/// What has been ingested so far, keyed by file path
+#[derive(Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub size: u64,
+    pub modified: u64,   // Seconds since epoch - a changed file is ingested again from the start
+    pub offset: usize,   // Bytes of the file's text already learned
+    #[serde(default)]
+    pub skipped: bool,   // Not UTF-8 text - left out until the file changes
+}
+
+impl Manifest {
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        write_atomic(path, &json)
+    }
+}
+
+/// One file still (partly) to be ingested
+pub struct PlannedFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: u64,
+    pub resume_offset: usize,
+}
+
+pub struct Ingestor {
+    pub config: IngestConfig,
+    pub manifest: Manifest,
+}
+
+impl Ingestor {
+    pub fn new(config: IngestConfig) -> Self {
+        let manifest = Manifest::load(&config.manifest_path);
+        Ingestor { config, manifest }
+    }
+
+    /// Files matching the config that aren't fully ingested yet, in ingestion order
+    pub fn plan(&self) -> Vec<PlannedFile> {
+        let mut planned = Vec::new();
+
+        for root in &self.config.roots {
+            if !root.exists() {
+                continue;
+            }
+
+            let mut paths: Vec<PathBuf> = walkdir::WalkDir::new(root)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .map(|e| e.path().to_path_buf())
+                .filter(|p| self.matches(p))
+                .collect();
+            paths.sort(); // Sort within root
+
+            for path in paths {
+                let Ok(meta) = fs::metadata(&path) else { continue };
+                let size = meta.len();
+                let modified = meta.modified().ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+
+                let resume_offset = match self.manifest.files.get(&manifest_key(&path)) {
+                    Some(entry) if entry.size == size && entry.modified == modified => {
+                        if entry.offset as u64 >= size {
+                            continue; // Fully ingested
+                        }
+                        entry.offset
+                    }
+                    _ => 0,
+                };
+
+                planned.push(PlannedFile { path, size, modified, resume_offset });
+            }
+        }
+
+        planned
+    }
+
+    /// Feed every planned chunk to `learn`. `checkpoint` is called every checkpoint_every chunks
+    /// and after each file; the manifest is only advanced once it succeeds, so a crash never
+    /// skips text the mind didn't save.
+    pub fn run<L, C, E>(&mut self, mut learn: L, mut checkpoint: C) -> Result<usize, E>
+    where
+        L: FnMut(&str),
+        C: FnMut() -> Result<(), E>,
+    {
+        let planned = self.plan();
+        if planned.is_empty() {
+            println!("nothing new to ingest");
+            return Ok(0);
+        }
+
+        let total_bytes: u64 = planned.iter().map(|f| f.size - (f.resume_offset as u64).min(f.size)).sum();
+        let mut done_bytes: u64 = 0;
+        let start = Instant::now();
+        let mut files_done = 0;
+
+        println!("\n📖 ingesting {} file(s), {} bytes...\n", planned.len(), total_bytes);
+
+        for (i, file) in planned.iter().enumerate() {
+            let content = match fs::read_to_string(&file.path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                    println!("[{}/{}] {} skipped: not UTF-8 text", i + 1, planned.len(), file.path.display());
+                    self.record(file, file.size as usize, true);
+                    continue;
+                }
+                Err(e) => {
+                    println!("[{}/{}] {} error: {}", i + 1, planned.len(), file.path.display(), e);
+                    continue;
+                }
+            };
+
+            // Offsets are always chunk ends, so always char boundaries - guard against edits anyway
+            let mut offset = file.resume_offset.min(content.len());
+            if !content.is_char_boundary(offset) {
+                offset = 0;
+            }
+
+            println!("[{}/{}] {} ({} bytes{})",
+                i + 1, planned.len(),
+                file.path.display(),
+                content.len(),
+                if offset > 0 { format!(", resuming at {}", offset) } else { String::new() }
+            );
+
+            let mut since_checkpoint = 0;
+            for (start_idx, end_idx) in chunk_bounds(&content[offset..], self.config.chunking) {
+                learn(&content[offset + start_idx..offset + end_idx]);
+                done_bytes += (end_idx - start_idx) as u64;
+                since_checkpoint += 1;
+
+                if since_checkpoint >= self.config.checkpoint_every {
+                    checkpoint()?;
+                    self.record(file, offset + end_idx, false);
+                    since_checkpoint = 0;
+                    print_progress(done_bytes, total_bytes, start);
+                }
+            }
+
+            if since_checkpoint > 0 {
+                checkpoint()?;
+                print_progress(done_bytes, total_bytes, start);
+            }
+            self.record(file, content.len(), false);
+            files_done += 1;
+        }
+
+        Ok(files_done)
+    }
+
+    fn record(&mut self, file: &PlannedFile, offset: usize, skipped: bool) {
+        self.manifest.files.insert(manifest_key(&file.path), ManifestEntry {
+            size: file.size,
+            modified: file.modified,
+            offset,
+            skipped,
+        });
+        if let Err(e) = self.manifest.save(&self.config.manifest_path) {
+            println!("warning: could not write ingest manifest: {}", e);
+        }
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        if self.config.include.is_empty() {
+            return true;
+        }
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        self.config.include.iter().any(|pattern| glob_match(pattern, name))
+    }
+}
+
+/// Byte ranges of each chunk of `text` - every boundary is a char boundary
+pub fn chunk_bounds(text: &str, chunking: Chunking) -> Vec<(usize, usize)> {
+    let (segments, max_bytes) = match chunking {
+        Chunking::Bytes { max_bytes } => (vec![(0, text.len())], max_bytes),
+        Chunking::Sentences { max_bytes } => (split_after(text, |rest| {
+            let mut chars = rest.chars();
+            match chars.next() {
+                Some('.' | '!' | '?') if chars.next().is_none_or(char::is_whitespace) => Some(1),
+                Some(c @ ('。' | '！' | '？')) => Some(c.len_utf8()),
+                _ => None,
+            }
+        }), max_bytes),
+        Chunking::Paragraphs { max_bytes } => (split_after(text, |rest| rest.starts_with("\n\n").then_some(2)), max_bytes),
+    };
+    let max_bytes = max_bytes.max(4); // Room for any single char
+
+    // Pack whole segments up to max_bytes, cutting oversized segments at char boundaries
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0;
+    let mut chunk_end = 0;
+
+    for (_, seg_end) in segments {
+        if seg_end - chunk_start > max_bytes && chunk_end > chunk_start {
+            chunks.push((chunk_start, chunk_end));
+            chunk_start = chunk_end;
+        }
+
+        while seg_end - chunk_start > max_bytes {
+            let mut cut = chunk_start + max_bytes;
+            while !text.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            chunks.push((chunk_start, cut));
+            chunk_start = cut;
+        }
+        chunk_end = seg_end;
+    }
+
+    if chunk_end > chunk_start {
+        chunks.push((chunk_start, chunk_end));
+    }
+
+    chunks
+}
+
+// Segments of `text` ending right after each delimiter; `delimiter` returns its byte length
+fn split_after(text: &str, delimiter: impl Fn(&str) -> Option<usize>) -> Vec<(usize, usize)> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+
+    for (i, _) in text.char_indices() {
+        if i < start {
+            continue;
+        }
+        if let Some(len) = delimiter(&text[i..]) {
+            let end = i + len;
+            segments.push((start, end));
+            start = end;
+        }
+    }
+
+    if start < text.len() {
+        segments.push((start, text.len()));
+    }
+    segments
+}
+
+/// `*` matches any run of characters, `?` exactly one
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn manifest_key(path: &Path) -> String {
+    path.to_string_lossy().to_string()
+}
+
+fn print_progress(done: u64, total: u64, start: Instant) {
+    let fraction = if total == 0 { 1.0 } else { done as f64 / total as f64 };
+    let elapsed = start.elapsed().as_secs_f64();
+    let eta = if fraction > 0.0 { elapsed / fraction - elapsed } else { 0.0 };
+    println!("   {:>5.1}%  {}/{} bytes  elapsed {}  eta {}",
+        fraction * 100.0, done, total, format_secs(elapsed), format_secs(eta));
+}
+
+fn format_secs(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn non_utf8_files_are_recorded_and_not_retried() {
+        let dir = std::env::temp_dir().join(format!("fractal_ingest_test_{}", std::process::id()));
+        let root = dir.join("texts");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("good.txt"), "One sentence. Another one.").unwrap();
+        fs::write(root.join("binary.txt"), [0x66, 0x6f, 0xff, 0xfe, 0x6f]).unwrap();
+
+        let config = IngestConfig {
+            roots: vec![root],
+            manifest_path: dir.join("manifest.json"),
+            ..IngestConfig::default()
+        };
+
+        let mut learned = Vec::new();
+        let files = Ingestor::new(config.clone())
+            .run(|chunk| learned.push(chunk.to_string()), || Ok::<(), ()>(()))
+            .unwrap();
+        let resumed = Ingestor::new(config);
+        let remaining = resumed.plan().len();
+        let skipped = resumed.manifest.files.values().filter(|entry| entry.skipped).count();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(files, 1);
+        assert_eq!(learned.concat(), "One sentence. Another one.");
+        assert_eq!(remaining, 0);
+        assert_eq!(skipped, 1);
+    }
+
+    fn chunks(text: &str, chunking: Chunking) -> Vec<&str> {
+        chunk_bounds(text, chunking).into_iter().map(|(start, end)| &text[start..end]).collect()
+    }
+
+    #[test]
+    fn chunks_never_split_multibyte_characters() {
+        let text = "日本語のテキスト。二番目の文です！😀😀 emoji ok. Third one?\n\nNew paragraph 段落。";
+        for max_bytes in [1, 2, 4, 5, 7, 10, 1000] {
+            for chunking in [Chunking::Bytes { max_bytes }, Chunking::Sentences { max_bytes }, Chunking::Paragraphs { max_bytes }] {
+                let bounds = chunk_bounds(text, chunking);
+                let mut expected_start = 0;
+                for &(start, end) in &bounds {
+                    assert_eq!(start, expected_start, "{:?}", chunking);
+                    assert!(text.is_char_boundary(start) && text.is_char_boundary(end), "{:?}", chunking);
+                    assert!(end > start && end - start <= max_bytes.max(4), "{:?}", chunking);
+                    expected_start = end;
+                }
+                assert_eq!(expected_start, text.len());
+                assert_eq!(chunks(text, chunking).concat(), text);
+            }
+        }
+    }
+
+    #[test]
+    fn sentences_and_paragraphs_are_kept_whole() {
+        let text = "One. Two! Three? Pi is 3.14 here.";
+        assert_eq!(chunks(text, Chunking::Sentences { max_bytes: 7 }), ["One.", " Two!", " Three?", " Pi is ", "3.14 he", "re."]);
+        assert_eq!(chunks(text, Chunking::Sentences { max_bytes: 17 }), ["One. Two! Three?", " Pi is 3.14 here."]);
+        assert_eq!(chunks("一。二。", Chunking::Sentences { max_bytes: 6 }), ["一。", "二。"]);
+
+        let text = "a b\n\nc d\n\ne";
+        assert_eq!(chunks(text, Chunking::Paragraphs { max_bytes: 5 }), ["a b\n\n", "c d\n\n", "e"]);
+        assert_eq!(chunks(text, Chunking::Paragraphs { max_bytes: 100 }), [text]);
+        assert!(chunk_bounds("", Chunking::Paragraphs { max_bytes: 100 }).is_empty());
+    }
+
+    #[test]
+    fn glob_wildcards_and_backtracking() {
+        assert!(glob_match("*.txt", "notes.txt"));
+        assert!(!glob_match("*.txt", "notes.txt.bak"));
+        assert!(glob_match("?.rs", "a.rs"));
+        assert!(!glob_match("?.rs", "ab.rs"));
+        assert!(glob_match("?", "日"));
+        assert!(glob_match("*ab*ab", "xabyab"));
+        assert!(glob_match("a*b*c", "aXbYbbc"));
+        assert!(!glob_match("a*b*c", "aXbYbcd"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("", ""));
+        assert!(!glob_match("", "a"));
+    }
+
+    #[test]
+    fn interrupted_run_resumes_from_the_manifest_offset() {
+        let dir = std::env::temp_dir().join(format!("fractal_ingest_resume_test_{}", std::process::id()));
+        let root = dir.join("texts");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("story.txt"), "First. Second. Third.").unwrap();
+
+        let config = IngestConfig {
+            roots: vec![root],
+            chunking: Chunking::Sentences { max_bytes: 8 },
+            manifest_path: dir.join("manifest.json"),
+            checkpoint_every: 1,
+            ..IngestConfig::default()
+        };
+
+        // The second checkpoint fails, as if the process died before saving
+        let mut checkpoints = 0;
+        let interrupted = Ingestor::new(config.clone()).run(|_| {}, || {
+            checkpoints += 1;
+            if checkpoints > 1 { Err(()) } else { Ok(()) }
+        });
+
+        let mut resumed = Ingestor::new(config);
+        let resume_offset = resumed.plan()[0].resume_offset;
+        let mut learned = Vec::new();
+        let files = resumed.run(|chunk| learned.push(chunk.to_string()), || Ok::<(), ()>(())).unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        assert!(interrupted.is_err());
+        assert_eq!(resume_offset, "First.".len());
+        assert_eq!(files, 1);
+        assert_eq!(learned, [" Second.", " Third."]);
+    }
+}
+
@@ -5,21 +5,64 @@ pub mod mind;
 pub mod llm;
 pub mod visualizer;
 pub mod camera;
+pub mod frame_source;
+#[cfg(all(feature = "v4l2", target_os = "linux"))]
+pub mod v4l2;
 pub mod audio;
+pub mod audio_source;
+pub mod spectral;
+pub mod pitch;
+pub mod onset;
 pub mod image;
+pub mod color;
+pub mod edges;
+pub mod texture;
+pub mod video;
+pub mod spatial;
+pub mod hamming;
+pub mod persist;
+pub mod config;
+pub mod rng;
+pub mod hashing;
+pub mod text_coords;
+pub mod ingest;
+pub mod sensory;
 
 pub use math::{C, Param, param_to_c};
 pub use fractal::{
     Fingerprint, MandelbrotCoord, julia_escapes, mandelbrot_escapes,
-    julia_fingerprint_from_mandelbrot, hash_to_mandelbrot_coord,
-    contextual_mandelbrot_coord, mandelbrot_stability, hamming_distance
+    julia_fingerprint_from_mandelbrot, hash_to_mandelbrot_coord, hash_to_mandelbrot_coord_with,
+    boundary_mandelbrot_coord_with, in_boundary_band,
+    contextual_mandelbrot_coord, contextual_mandelbrot_coord_with, plane_to_mandelbrot_coord,
+    mandelbrot_stability, hamming_distance
 };
 pub use memory::{FractalSymbol, ConceptTrajectory, AssociativeField};
-pub use mind::FractalMind;
+pub use mind::{FractalMind, StrengthPolicy, MergeError};
+pub use persist::MindStateError;
+pub use config::MindConfig;
+pub use rng::MindRng;
+pub use hashing::{CoordHasher, CoordHasherKind, SipHash13, Fnv1a};
+pub use text_coords::{TextCoordMapper, CollisionStats, ngram_to_mandelbrot_coord, collision_stats};
+pub use spatial::SpatialIndex;
+pub use hamming::{HammingIndex, linear_first_within};
+pub use ingest::{Ingestor, IngestConfig, Chunking};
 pub use llm::LLMBridge;
 pub use visualizer::spawn_visualizer;
 
 // Sensory input encoders
 pub use camera::{CameraEncoder, FrameEncoding};
+pub use frame_source::{Frame, FrameSource, RawRgbFile, ImageDirectory, SyntheticSource};
 pub use audio::{AudioEncoder, AudioEncoding};
-pub use image::{encode_image, encode_image_data, ImageEncoding};
\ No newline at end of file
+pub use audio_source::{AudioSource, AudioFile, Microphone};
+pub use spectral::{SpectralFrontEnd, WindowFunction, MelFilterbank};
+pub use pitch::{PitchEstimate, Note, yin};
+pub use onset::{Onset, Hit, OnsetAnalysis};
+pub use image::{
+    encode_image, encode_image_data, encode_image_multi, encode_image_data_multi,
+    image_vector, pixel_grid_vector, channel_vector,
+    ImageEncoding, ImageChannel, HistogramSpace, VideoEncoder
+};
+pub use color::{ColorCluster, kmeans_lab, srgb_to_lab, lab_to_srgb, rgb_to_hsv};
+pub use edges::EdgeOptions;
+pub use texture::{CellTexture, GaborBank};
+pub use sensory::{SensoryVector, SensoryProjection, sensory_to_mandelbrot_coord};
\ No newline at end of file
@@ -1,22 +1,59 @@
 use std::io::{self, Write};
-use std::sync::{Arc, Mutex};
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+use rand::Rng;
 
 use version_004::{
-    FractalMind, LLMBridge, spawn_visualizer,
-    CameraEncoder, FrameEncoding,
+    FractalMind, MindConfig, MindStateError, StrengthPolicy, TextCoordMapper, MindRng, collision_stats,
+    LLMBridge, spawn_visualizer,
+    CameraEncoder, FrameEncoding, RawRgbFile, ImageDirectory, SyntheticSource,
     AudioEncoder, AudioEncoding,
-    encode_image, ImageEncoding
+    encode_image, encode_image_multi, image_vector, sensory_to_mandelbrot_coord, ImageEncoding, VideoEncoder, persist,
+    Ingestor, IngestConfig, Chunking
 };
 
+const MIND_FILE: &str = "mind_state.bin";
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mind = match FractalMind::load("mind_state.bin") {
+    let mut mind = match FractalMind::load(MIND_FILE) {
         Ok(m) => m,
-        Err(_) => FractalMind::new()
+        Err(MindStateError::Io(e)) if e.kind() == io::ErrorKind::NotFound => FractalMind::new(),
+        Err(e) => {
+            // Don't start fresh - autosave would overwrite the trained mind
+            println!("could not load mind_state.bin: {}", e);
+            println!("move it aside to start a new mind\n");
+            return Err(e.into());
+        }
     };
 
+    // --seed restarts the mind's random stream for a replayable run
+    let seed_arg = cli_arg("--seed");
+    if let Some(seed) = &seed_arg {
+        let seed: u64 = seed.parse().map_err(|_| format!("--seed expects an integer, got '{}'", seed))?;
+        mind.reseed(seed);
+        println!("seeded with {}", seed);
+    }
+
+    // Hyperparameters from --config override the ones saved with the mind
+    if let Some(path) = cli_arg("--config") {
+        mind.set_config(MindConfig::from_file(&path)?);
+        println!("using config from {}", path);
+    }
+
+    // --text-coords picks how a new mind places strings; a trained mind keeps its own
+    if let Some(mapper) = cli_arg("--text-coords") {
+        let mapper: TextCoordMapper = mapper.parse()?;
+        if mind.trajectories.is_empty() {
+            mind.text_mapper = mapper;
+            println!("text coordinates: {}", mapper);
+        } else if mind.text_mapper != mapper {
+            println!("--text-coords ignored: this mind was trained with {} coordinates", mind.text_mapper);
+        }
+    }
+
     let mind = Arc::new(Mutex::new(mind));
     let llm = Arc::new(LLMBridge::new("mistral:7b"));
     let dreaming = Arc::new(AtomicBool::new(false));
@@ -24,8 +61,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Spawn visual window
     spawn_visualizer(Arc::clone(&mind));
 
-    // Spawn background thought thread
-    spawn_background_thought(Arc::clone(&mind), Arc::clone(&llm), Arc::clone(&dreaming));
+    // Spawn background thought thread - not in a seeded run, where its timing-dependent
+    // draws from the mind's rng (and its wandering coordinate) would break replay
+    if seed_arg.is_some() {
+        println!("background thought off - seeded runs replay input only");
+    } else {
+        spawn_background_thought(Arc::clone(&mind), Arc::clone(&llm), Arc::clone(&dreaming));
+    }
 
     println!("\n╭──────────────────────────────────────────╮");
     println!("│      fractal mind v2.0 + LLM bridge      │");
@@ -36,14 +78,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("│                                          │");
     println!("│ /state  /alphabet  /reset  /save  /quit  │");
     println!("│ /train <rounds>  - train mind with LLM  │");
-    println!("│ /read              - ingest text file    │");
+    println!("│ /read [dirs] [--glob p] - ingest text    │");
     println!("│ /dream             - toggle LLM dreams   │");
-    println!("│ /camera            - train from camera   │");
+    println!("│ /camera [source]   - train from camera   │");
     println!("│ /audio             - train from audio    │");
+    println!("│ /audiofile <path>  - learn a WAV file    │");
     println!("│ /image <path>      - train from image    │");
     println!("│ /images            - batch process dir   │");
+    println!("│ /sense <path>      - image as numbers    │");
+    println!("│ /recall <path>     - images by color     │");
+    println!("│ /video <path> [skip] - learn a clip      │");
     println!("│ /learn             - supervised learning │");
     println!("│ /imagine           - visualize concept   │");
+    println!("│ /restore [n]       - list/load snapshot  │");
+    println!("│ /export [file]     - write mind as JSON  │");
+    println!("│ /import <file>     - load mind from JSON │");
+    println!("│ /merge <file> [max|sum|mean] - merge in │");
+    println!("│ /coordstats [n]    - symbol collisions   │");
     println!("│                                          │");
     println!("│ (background thought always active)       │");
     println!("╰──────────────────────────────────────────╯\n");
@@ -101,38 +152,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
                 continue;
             }
-            "/camera" => {
-                println!("\n📷 starting camera feed training (press Ctrl+C to stop)...\n");
-
-                match CameraEncoder::new(FrameEncoding::EdgeSymbols) {
-                    Ok(mut encoder) => {
-                        println!("camera initialized - feeding edge patterns to fractal mind\n");
-
-                        for i in 0..100 {  // 100 frames
-                            match encoder.capture_frame() {
-                                Ok(encoded) => {
-                                    mind.lock().unwrap().process_with_intensity(&encoded, 0.2);
-                                    println!("[{}] processed frame ({} bytes)", i+1, encoded.len());
-                                    std::thread::sleep(Duration::from_millis(100));
-                                }
-                                Err(e) => {
-                                    println!("frame capture error: {}", e);
-                                    break;
-                                }
-                            }
-                        }
-                        println!("\n✓ camera training complete\n");
-                        mind.lock().unwrap().save("mind_state.bin")?;
-                    }
-                    Err(e) => println!("camera error: {}\n", e),
-                }
-                continue;
-            }
             "/audio" => {
                 println!("\n🎤 starting audio feed training (10 seconds)...\n");
 
                 match AudioEncoder::new(AudioEncoding::BandSymbols { bands: 8 }) {
-                    Ok(encoder) => {
+                    Ok(mut encoder) => {
                         println!("audio initialized - feeding frequency bands to fractal mind\n");
 
                         for i in 0..100 {  // 10 seconds at 100ms intervals
@@ -143,97 +167,245 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
 
                         println!("\n✓ audio training complete\n");
-                        mind.lock().unwrap().save("mind_state.bin")?;
+                        save_mind(&mind.lock().unwrap())?;
                     }
                     Err(e) => println!("audio error: {}\n", e),
                 }
                 continue;
             }
-            "/read" => {
-                let base_path = "data";
-
-                if !std::path::Path::new(base_path).exists() {
-                    println!("error: data/ directory does not exist\n");
+            "/reset" => {
+                *mind.lock().unwrap() = FractalMind::new();
+                println!("fractal mind reset\n");
+                continue;
+            }
+            "/save" => {
+                save_mind(&mind.lock().unwrap())?;
+                continue;
+            }
+            "/restore" => {
+                let snapshots = persist::list_snapshots(Path::new(MIND_FILE))?;
+                if snapshots.is_empty() {
+                    println!("no snapshots yet\n");
                     continue;
                 }
+                println!("\nsnapshots (newest first):");
+                for (i, (path, taken)) in snapshots.iter().enumerate() {
+                    let age = SystemTime::now().duration_since(*taken).unwrap_or_default().as_secs();
+                    println!("{}. {} ({} min ago)", i + 1, path.display(), age / 60);
+                }
+                println!("use /restore <n> to load one\n");
+                continue;
+            }
+            _ => {
+                if let Some(arg) = input.strip_prefix("/restore ") {
+                    let snapshots = persist::list_snapshots(Path::new(MIND_FILE))?;
+                    let choice = arg.trim().parse::<usize>().ok()
+                        .and_then(|n| n.checked_sub(1))
+                        .and_then(|i| snapshots.get(i));
+
+                    let Some((path, _)) = choice else {
+                        println!("usage: /restore <n> (see /restore for the list)\n");
+                        continue;
+                    };
+
+                    match FractalMind::load(&path.to_string_lossy()) {
+                        Ok(restored) => {
+                            // Keep the state we're replacing, regardless of snapshot interval
+                            persist::snapshot(Path::new(MIND_FILE), snapshot_count())?;
+                            let mut m = mind.lock().unwrap();
+                            *m = restored;
+                            save_mind(&m)?;
+                            println!("restored {}\n", path.display());
+                        }
+                        Err(e) => println!("restore error: {}\n", e),
+                    }
+                    continue;
+                }
+
+                if input == "/read" || input.starts_with("/read ") {
+                    // /read [root ...] [--glob <pattern>]... [--chunk sentences|paragraphs|bytes] [--fresh]
+                    let mut config = IngestConfig::default();
+                    let mut roots = Vec::new();
+                    let mut fresh = false;
+                    let mut args = input.split_whitespace().skip(1);
+                    let mut bad_arg = None;
+
+                    while let Some(arg) = args.next() {
+                        match arg {
+                            "--glob" => match args.next() {
+                                Some(pattern) => config.include.push(pattern.to_string()),
+                                None => bad_arg = Some("--glob needs a pattern".to_string()),
+                            },
+                            "--chunk" => match args.next() {
+                                Some("sentences") => config.chunking = Chunking::Sentences { max_bytes: 10000 },
+                                Some("paragraphs") => config.chunking = Chunking::Paragraphs { max_bytes: 10000 },
+                                Some("bytes") => config.chunking = Chunking::Bytes { max_bytes: 10000 },
+                                other => bad_arg = Some(format!("unknown chunking '{}' (sentences, paragraphs, bytes)", other.unwrap_or(""))),
+                            },
+                            "--fresh" => fresh = true,
+                            root => roots.push(std::path::PathBuf::from(root)),
+                        }
+                    }
 
-                // Process folders 00-07 in order (easiest to hardest)
-                let mut all_paths = Vec::new();
-                for i in 0..8 {
-                    let folder = format!("{}/{:02}", base_path, i);
-                    if std::path::Path::new(&folder).exists() {
-                        let mut folder_paths: Vec<_> = walkdir::WalkDir::new(&folder)
-                            .into_iter()
-                            .filter_map(|e| e.ok())
-                            .filter(|e| e.file_type().is_file())
-                            .map(|e| e.path().to_path_buf())
-                            .collect();
-                        folder_paths.sort(); // Sort within folder
-                        all_paths.extend(folder_paths);
+                    if let Some(message) = bad_arg {
+                        println!("{}\n", message);
+                        continue;
+                    }
+                    if !roots.is_empty() {
+                        config.roots = roots;
                     }
+                    if fresh {
+                        let _ = std::fs::remove_file(&config.manifest_path);
+                    }
+
+                    let mut ingestor = Ingestor::new(config);
+                    let result = ingestor.run(
+                        |chunk| {
+                            mind.lock().unwrap().process_input(chunk);
+                            // Release lock briefly after each chunk for visualizer + background thought
+                            std::thread::sleep(Duration::from_millis(10));
+                        },
+                        || save_mind(&mind.lock().unwrap()),
+                    );
+
+                    match result {
+                        Ok(files) => println!("\n✓ ingested {} files\n", files),
+                        Err(e) => println!("\nsave error, stopping ingestion: {}\n", e),
+                    }
+                    continue;
                 }
 
-                if all_paths.is_empty() {
-                    println!("no files found in data/00 through data/07\n");
+                if input == "/camera" || input.starts_with("/camera ") {
+                    // /camera [/dev/videoN | <frame dir> | <file.rgb> <w>x<h> | synthetic]
+                    println!("\n📷 starting camera feed training (press Ctrl+C to stop)...\n");
+
+                    let encoding = FrameEncoding::EdgeSymbols { width: 40, height: 40 };
+                    let args: Vec<&str> = input.split_whitespace().skip(1).collect();
+                    let encoder: Result<CameraEncoder, Box<dyn std::error::Error>> = match args.as_slice() {
+                        [] => CameraEncoder::new(encoding),
+                        ["synthetic"] => Ok(CameraEncoder::with_source(encoding, Box::new(SyntheticSource::new(160, 120)))),
+                        [dir] if Path::new(dir).is_dir() => ImageDirectory::open(dir, false)
+                            .map(|source| CameraEncoder::with_source(encoding, Box::new(source))),
+                        [device] => CameraEncoder::open_device(encoding, device),
+                        [file, size] => match parse_size(size) {
+                            Some((w, h)) => RawRgbFile::open(file, w, h, false)
+                                .map(|source| CameraEncoder::with_source(encoding, Box::new(source))),
+                            None => Err(format!("bad frame size '{}' (expected WIDTHxHEIGHT)", size).into()),
+                        },
+                        _ => Err("usage: /camera [/dev/videoN | <frame dir> | <file.rgb> <w>x<h> | synthetic]".into()),
+                    };
+
+                    match encoder {
+                        Ok(mut encoder) => {
+                            println!("camera initialized - feeding edge patterns to fractal mind\n");
+
+                            for i in 0..100 {  // 100 frames
+                                match encoder.capture_frame() {
+                                    Ok(encoded) => {
+                                        mind.lock().unwrap().process_with_intensity(&encoded, 0.2);
+                                        println!("[{}] processed frame ({} bytes)", i+1, encoded.len());
+                                        std::thread::sleep(Duration::from_millis(100));
+                                    }
+                                    Err(e) => {
+                                        println!("frame capture error: {}", e);
+                                        break;
+                                    }
+                                }
+                            }
+                            println!("\n✓ camera training complete\n");
+                            save_mind(&mind.lock().unwrap())?;
+                        }
+                        Err(e) => println!("camera error: {}\n", e),
+                    }
                     continue;
                 }
 
-                println!("\n📖 reading {} file(s) from data/00-07 (easiest→hardest)...\n", all_paths.len());
+                if input == "/export" || input.starts_with("/export ") {
+                    let path = input.split_whitespace().nth(1).unwrap_or("mind_state.json");
+                    if let Err(e) = mind.lock().unwrap().export_json(path) {
+                        println!("export error: {}\n", e);
+                    }
+                    continue;
+                }
 
-                let paths = all_paths;
+                if let Some(path) = input.strip_prefix("/import ") {
+                    match FractalMind::import_json(path.trim()) {
+                        Ok(imported) => {
+                            let mut m = mind.lock().unwrap();
+                            *m = imported;
+                            save_mind(&m)?;
+                        }
+                        Err(e) => println!("import error: {}\n", e),
+                    }
+                    continue;
+                }
 
-                for (i, file_path) in paths.iter().enumerate() {
-                    match std::fs::read_to_string(file_path) {
-                        Ok(content) => {
-                            println!("[{}/{}] {} ({} bytes)",
-                                i+1, paths.len(),
-                                file_path.display(),
-                                content.len()
-                            );
+                if let Some(args) = input.strip_prefix("/merge ") {
+                    let mut args = args.split_whitespace();
+                    let path = args.next().unwrap_or("");
+                    let policy = match args.next().unwrap_or("max") {
+                        "max" => StrengthPolicy::Max,
+                        "sum" => StrengthPolicy::Sum,
+                        "mean" => StrengthPolicy::Mean,
+                        other => {
+                            println!("unknown strength policy '{}' (max, sum, mean)\n", other);
+                            continue;
+                        }
+                    };
+
+                    match FractalMind::load(path) {
+                        Ok(other) => {
+                            let mut m = mind.lock().unwrap();
+                            match m.merge_with(other, policy) {
+                                Ok(()) => save_mind(&m)?,
+                                Err(e) => println!("merge error: {}\n", e),
+                            }
+                        }
+                        Err(e) => println!("merge error: {}\n", e),
+                    }
+                    continue;
+                }
 
-                            // Process in chunks to allow background thought
-                            const CHUNK_SIZE: usize = 10000;
-                            let chunks: Vec<&str> = content
-                                .as_bytes()
-                                .chunks(CHUNK_SIZE)
-                                .map(|chunk| std::str::from_utf8(chunk).unwrap_or(""))
-                                .collect();
-
-                            for (chunk_idx, chunk) in chunks.iter().enumerate() {
-                                {
-                                    mind.lock().unwrap().process_input(chunk);
-                                }
-                                // Release lock briefly after each chunk for visualizer + background thought
-                                std::thread::sleep(Duration::from_millis(10));
+                if let Some(path) = input.strip_prefix("/audiofile ") {
+                    let path = path.trim();
+                    println!("\n🎵 reading audio file: {}\n", path);
 
-                                // Auto-save every 50 chunks
-                                if chunk_idx % 50 == 0 && chunk_idx > 0 {
-                                    let _ = mind.lock().unwrap().save("mind_state.bin");
-                                }
+                    match AudioEncoder::from_file(path, AudioEncoding::BandSymbols { bands: 8 }) {
+                        Ok(mut encoder) => {
+                            let mut windows = Vec::new();
+                            while let Some(encoded) = encoder.encode_next() {
+                                println!("[{}] {}", windows.len() + 1, &encoded[..40.min(encoded.len())]);
+                                windows.push(encoded);
                             }
 
-                            // Save after every file
-                            let _ = mind.lock().unwrap().save("mind_state.bin");
+                            mind.lock().unwrap().learn_sequence(&windows, 0.15);
+                            println!("\n✓ {} audio windows learned as a temporal trajectory\n", windows.len());
+                            save_mind(&mind.lock().unwrap())?;
                         }
-                        Err(e) => println!("[{}/{}] error: {}", i+1, paths.len(), e),
+                        Err(e) => println!("audio error: {}\n", e),
                     }
+                    continue;
                 }
 
-                println!("\n✓ ingested {} files\n", paths.len());
-                mind.lock().unwrap().save("mind_state.bin")?;
-                continue;
-            }
-            "/reset" => {
-                *mind.lock().unwrap() = FractalMind::new();
-                println!("fractal mind reset\n");
-                continue;
-            }
-            "/save" => {
-                mind.lock().unwrap().save("mind_state.bin")?;
-                continue;
-            }
-            _ => {
-                if input.starts_with("/image") {
+                if let Some(args) = input.strip_prefix("/video ") {
+                    let mut args = args.split_whitespace();
+                    let path = args.next().unwrap_or("");
+                    let frame_skip = args.next().and_then(|n| n.parse().ok()).unwrap_or(0);
+
+                    println!("\n🎞️  decoding video: {}\n", path);
+
+                    match VideoEncoder::encode_video_frames(path, ImageEncoding::EdgeAscii { width: 40, height: 40 }, frame_skip) {
+                        Ok(frames) => {
+                            mind.lock().unwrap().learn_sequence(&frames, 0.3);
+                            println!("✓ {} frames learned as a temporal trajectory\n", frames.len());
+                            save_mind(&mind.lock().unwrap())?;
+                        }
+                        Err(e) => println!("video error: {}\n", e),
+                    }
+                    continue;
+                }
+
+                if input == "/image" || input.starts_with("/image ") {
                     let path = input.split_whitespace()
                         .nth(1)
                         .unwrap_or("");
@@ -245,11 +417,52 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                     println!("\n🖼️  processing image: {}\n", path);
 
-                    match encode_image(path, ImageEncoding::EdgeAscii { width: 40, height: 40 }) {
-                        Ok(encoded) => {
-                            mind.lock().unwrap().process_with_intensity(&encoded, 0.3);
-                            println!("✓ image encoded and processed ({} bytes)\n", encoded.len());
-                            mind.lock().unwrap().save("mind_state.bin")?;
+                    match encode_image_multi(path, &ImageEncoding::default_channels()) {
+                        Ok(channels) => {
+                            let encoded: Vec<String> = channels.into_iter().map(|c| c.encoded).collect();
+                            mind.lock().unwrap().learn_channels(&encoded, 0.3, Some(path.to_string()));
+                            println!("✓ image encoded and processed ({} channels, {} bytes)\n",
+                                     encoded.len(), encoded.iter().map(String::len).sum::<usize>());
+                            save_mind(&mind.lock().unwrap())?;
+                        }
+                        Err(e) => println!("image error: {}\n", e),
+                    }
+                    continue;
+                }
+
+                if let Some(path) = input.strip_prefix("/recall ") {
+                    let path = path.trim();
+
+                    match encode_image(path, ImageEncoding::DominantColors { color_count: 5 }) {
+                        Ok(colors) => {
+                            let recalled = mind.lock().unwrap().recall_images_by_channel(&colors, 5);
+                            if recalled.is_empty() {
+                                println!("\nno learned image has colors like {}\n", path);
+                            } else {
+                                println!("\n✓ colors like {}: {}\n", path, recalled.join(", "));
+                            }
+                        }
+                        Err(e) => println!("image error: {}\n", e),
+                    }
+                    continue;
+                }
+
+                if let Some(path) = input.strip_prefix("/sense ") {
+                    let path = path.trim();
+
+                    match image_vector(path, 16, 16) {
+                        Ok(vector) => {
+                            let mut mind = mind.lock().unwrap();
+                            let similar = mind.recall_images_by_sensory(&vector, 3);
+                            mind.learn_sensory(&vector, 0.3, Some(path.to_string()));
+
+                            let coord = sensory_to_mandelbrot_coord(&vector);
+                            println!("\n✓ sensed {} at ({:.3},{:.3})", path, coord.re, coord.im);
+                            if !similar.is_empty() {
+                                println!("looks like: {}", similar.join(", "));
+                            }
+                            println!();
+                            save_mind(&mind)?;
                         }
                         Err(e) => println!("image error: {}\n", e),
                     }
@@ -282,13 +495,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     println!("\n📸 found {} images\n", image_paths.len());
 
                     for (i, img_path) in image_paths.iter().enumerate() {
-                        match encode_image(img_path, ImageEncoding::EdgeAscii { width: 40, height: 40 }) {
-                            Ok(encoded) => {
+                        match encode_image_multi(img_path, &ImageEncoding::default_channels()) {
+                            Ok(channels) => {
                                 println!("[{}/{}] {}", i+1, image_paths.len(), img_path.display());
-                                mind.lock().unwrap().process_with_intensity(&encoded, 0.2);
+                                let encoded: Vec<String> = channels.into_iter().map(|c| c.encoded).collect();
+                                mind.lock().unwrap().learn_channels(&encoded, 0.2, Some(img_path.to_string_lossy().to_string()));
 
                                 if (i + 1) % 10 == 0 {
-                                    let _ = mind.lock().unwrap().save("mind_state.bin");
+                                    let _ = save_mind(&mind.lock().unwrap());
                                 }
                             }
                             Err(e) => println!("[{}/{}] error: {}", i+1, image_paths.len(), e),
@@ -296,7 +510,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
 
                     println!("\n✓ processed {} images\n", image_paths.len());
-                    mind.lock().unwrap().save("mind_state.bin")?;
+                    save_mind(&mind.lock().unwrap())?;
                     continue;
                 }
 
@@ -338,10 +552,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         }
 
                         // Encode visual pattern while user looks at image
-                        match encode_image(&img_path, ImageEncoding::EdgeAscii { width: 60, height: 40 }) {
-                            Ok(encoded) => {
-                                // Process visual pattern (low intensity, just sensing)
-                                mind.lock().unwrap().process_with_intensity(&encoded, 0.15);
+                        let mut encodings = ImageEncoding::default_channels();
+                        encodings[0] = ImageEncoding::EdgeAscii { width: 60, height: 40 };
+
+                        match encode_image_multi(img_path, &encodings) {
+                            Ok(channels) => {
+                                let mut encoded: Vec<String> = channels.into_iter().map(|c| c.encoded).collect();
+
+                                // Process the shape (low intensity, just sensing)
+                                mind.lock().unwrap().process_with_intensity(&encoded[0], 0.15);
 
                                 // Mind tries to guess from fractal space
                                 let guess = mind.lock().unwrap().process_input("what is this");
@@ -361,15 +580,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                                     break;
                                 }
 
-                                // Learn: visual pattern + semantic label together (high intensity)
-                                // Store image path with the trajectory
-                                let combined = format!("{}\n{}", encoded, label);
-                                mind.lock().unwrap().learn_concept_with_image(&combined, 0.8, img_path.to_string_lossy().to_string());
+                                // Learn: every visual channel + semantic label, linked (high intensity)
+                                // Store image path with the trajectories
+                                encoded.push(label.to_string());
+                                mind.lock().unwrap().learn_channels(&encoded, 0.8, Some(img_path.to_string_lossy().to_string()));
 
                                 println!("✓ learned: {}\n", label);
 
                                 if (i + 1) % 5 == 0 {
-                                    let _ = mind.lock().unwrap().save("mind_state.bin");
+                                    let _ = save_mind(&mind.lock().unwrap());
                                 }
                             }
                             Err(e) => println!("encoding error: {}", e),
@@ -377,7 +596,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
 
                     println!("\n✓ learning session complete\n");
-                    mind.lock().unwrap().save("mind_state.bin")?;
+                    save_mind(&mind.lock().unwrap())?;
+                    continue;
+                }
+
+                if input == "/coordstats" || input.starts_with("/coordstats ") {
+                    let samples: usize = input.split_whitespace()
+                        .nth(1)
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(200);
+
+                    let (hasher, scale, threshold, current) = {
+                        let mind = mind.lock().unwrap();
+                        (mind.coord_hasher, mind.scale, mind.config.merge_threshold, mind.text_mapper)
+                    };
+                    let words = sample_words(samples);
+
+                    println!("\n{} random words, fingerprints at scale {}, merging below {} bits:\n", samples, scale, threshold);
+                    println!("  mapper     collisions   in band   crowding");
+                    for mapper in TextCoordMapper::ALL {
+                        let stats = collision_stats(mapper, &hasher, &words, scale, threshold);
+                        println!("  {:<9}  {:>9.1}%  {:>7.1}%  {:>9.3}{}",
+                                 mapper,
+                                 100.0 * stats.collision_rate(),
+                                 100.0 * stats.in_band as f64 / samples.max(1) as f64,
+                                 stats.crowding,
+                                 if mapper == current { "   (this mind)" } else { "" });
+                    }
+                    println!();
                     continue;
                 }
 
@@ -417,7 +663,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     }
 
                     println!("\n✓ training complete\n");
-                    mind.lock().unwrap().save("mind_state.bin")?;
+                    save_mind(&mind.lock().unwrap())?;
                     continue;
                 }
             }
@@ -434,14 +680,54 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         };
 
         if should_save {
-            let _ = mind.lock().unwrap().save("mind_state.bin");
+            let _ = save_mind(&mind.lock().unwrap());
         }
     }
 
-    mind.lock().unwrap().save("mind_state.bin")?;
+    save_mind(&mind.lock().unwrap())?;
     Ok(())
 }
 
+/// Reproducible made-up lowercase words of 3 to 10 letters, for /coordstats
+fn sample_words(count: usize) -> Vec<String> {
+    let mut rng = MindRng::from_seed(0);
+    (0..count)
+        .map(|_| {
+            let len = rng.gen_range(3..=10);
+            (0..len).map(|_| rng.gen_range(b'a'..=b'z') as char).collect()
+        })
+        .collect()
+}
+
+/// Value following `name` on the command line (e.g. `--config experiment.json`)
+fn cli_arg(name: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+/// Rotating snapshots to keep, from `--snapshots <n>`
+fn snapshot_count() -> usize {
+    static COUNT: OnceLock<usize> = OnceLock::new();
+    *COUNT.get_or_init(|| {
+        cli_arg("--snapshots")
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(persist::DEFAULT_SNAPSHOT_COUNT)
+    })
+}
+
+/// "640x480" -> (640, 480)
+fn parse_size(size: &str) -> Option<(usize, usize)> {
+    let (w, h) = size.split_once('x')?;
+    Some((w.parse().ok()?, h.parse().ok()?))
+}
+
+fn save_mind(mind: &FractalMind) -> Result<(), MindStateError> {
+    mind.save_with_snapshots(MIND_FILE, snapshot_count())
+}
+
 fn spawn_background_thought(
     mind: Arc<Mutex<FractalMind>>,
     llm: Arc<LLMBridge>,
@@ -494,7 +780,7 @@ fn spawn_background_thought(
             // Auto-save every 100 background iterations
             if iteration % 100 == 0 {
                 if let Ok(m) = mind.try_lock() {
-                    let _ = m.save("mind_state.bin");
+                    let _ = save_mind(&m);
                 }
             }
         }
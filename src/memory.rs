@@ -1,13 +1,20 @@
 use bincode::{Encode, Decode};
+use serde::{Serialize, Deserialize};
 use crate::fractal::{Fingerprint, MandelbrotCoord};
 
-#[derive(Clone, Encode, Decode)]
+/// Squared distance over which a trajectory's influence falls to 1/e of its strength
+const INFLUENCE_WIDTH: f64 = 0.05;
+
+#[derive(Clone, Encode, Decode, Serialize, Deserialize)]
 pub struct FractalSymbol {
     pub coord: MandelbrotCoord,
+    #[serde(with = "crate::fractal::fingerprint_hex")]
     pub pattern: Fingerprint,
     pub count: u32,
     pub label: Option<char>,
+    #[serde(with = "crate::persist::json_float")]
     pub confidence: f32,
+    #[serde(with = "crate::persist::json_float")]
     pub stability: f64,
 }
 
@@ -24,9 +31,10 @@ impl FractalSymbol {
     }
 }
 
-#[derive(Clone, Encode, Decode)]
+#[derive(Clone, Encode, Decode, Serialize, Deserialize)]
 pub struct ConceptTrajectory {
     pub path: Vec<MandelbrotCoord>,
+    #[serde(with = "crate::persist::json_float")]
     pub strength: f64,
     pub symbols: Vec<usize>,  // Indices into fractal symbol space (geometric patterns, not strings)
     pub image_path: Option<String>,  // Only for visual recall - path to source image
@@ -76,7 +84,21 @@ impl ConceptTrajectory {
 
     pub fn influence_at(&self, coord: MandelbrotCoord) -> f64 {
         let (_, dist) = self.closest_point(coord);
-        self.strength * (-dist * dist / 0.05).exp()
+        self.influence_at_distance(dist)
+    }
+
+    pub fn influence_at_distance(&self, dist: f64) -> f64 {
+        self.strength * Self::falloff(dist)
+    }
+
+    /// Share of a trajectory's strength felt at dist
+    pub fn falloff(dist: f64) -> f64 {
+        (-dist * dist / INFLUENCE_WIDTH).exp()
+    }
+
+    /// Distance beyond which influence_at drops below 0.1% of the trajectory's strength
+    pub fn influence_radius() -> f64 {
+        (INFLUENCE_WIDTH * 1000.0f64.ln()).sqrt()
     }
 
     pub fn suggest_next_coord(&self, current: MandelbrotCoord) -> Option<(MandelbrotCoord, usize)> {
@@ -95,11 +117,15 @@ impl ConceptTrajectory {
     }
 }
 
-#[derive(Clone, Encode, Decode)]
+#[derive(Clone, Encode, Decode, Serialize, Deserialize)]
 pub struct AssociativeField {
     pub center: MandelbrotCoord,
+    #[serde(with = "crate::persist::json_float")]
     pub radius: f64,
+    #[serde(with = "crate::persist::json_float")]
     pub strength: f64,
+    #[serde(default)]
+    pub members: Vec<usize>,  // Trajectories linked wherever they lie - channels of one percept
 }
 
 impl AssociativeField {
@@ -108,6 +134,15 @@ impl AssociativeField {
             center,
             radius,
             strength: 1.0,
+            members: Vec::new(),
+        }
+    }
+
+    /// A field that links trajectories in different regions (e.g. an image's edge and color channels)
+    pub fn linking(center: MandelbrotCoord, radius: f64, members: Vec<usize>) -> Self {
+        AssociativeField {
+            members,
+            ..Self::new(center, radius)
         }
     }
 
@@ -1,15 +1,69 @@
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 use bincode::{Encode, Decode};
+use serde::{Serialize, Deserialize};
 use rand::Rng;
 
 use crate::fractal::{
-    MandelbrotCoord, hash_to_mandelbrot_coord, contextual_mandelbrot_coord,
-    julia_fingerprint_from_mandelbrot, mandelbrot_stability, hamming_distance,
+    MandelbrotCoord, contextual_mandelbrot_coord_from,
+    julia_fingerprint_from_mandelbrot, mandelbrot_stability,
     find_nearby_interesting_points
 };
 use crate::memory::{FractalSymbol, ConceptTrajectory, AssociativeField};
+use crate::spatial::SpatialIndex;
+use crate::hamming::HammingIndex;
+use crate::sensory::{SensoryVector, sensory_to_mandelbrot_coord};
+use crate::image::channel_vector;
+use crate::persist::{self, MindStateError};
+use crate::config::MindConfig;
+use crate::rng::MindRng;
+use crate::hashing::CoordHasherKind;
+use crate::text_coords::{TextCoordMapper, ngram_to_mandelbrot_coord};
+
+/// How strengths combine when two minds hold the same trajectory or overlapping fields
+#[derive(Clone, Copy, Debug)]
+pub enum StrengthPolicy {
+    Max,
+    Sum,
+    Mean,
+}
+
+impl StrengthPolicy {
+    pub fn combine(self, a: f64, b: f64) -> f64 {
+        match self {
+            StrengthPolicy::Max => a.max(b),
+            StrengthPolicy::Sum => a + b,
+            StrengthPolicy::Mean => (a + b) / 2.0,
+        }
+    }
+}
+
+/// Why merge_with refused another mind
+#[derive(Debug)]
+pub enum MergeError {
+    /// The minds put the same strings at different coordinates (text mapper, hasher)
+    TextMapping {
+        ours: (TextCoordMapper, CoordHasherKind),
+        theirs: (TextCoordMapper, CoordHasherKind),
+    },
+}
 
-#[derive(Encode, Decode)]
+impl std::fmt::Display for MergeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeError::TextMapping { ours, theirs } => write!(
+                f,
+                "merged mind maps text with {}/{:?}, this one with {}/{:?} - the same words would sit in different places",
+                theirs.0, theirs.1, ours.0, ours.1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+#[derive(Encode, Decode, Serialize, Deserialize)]
 pub struct FractalMind {
     pub current_coord: MandelbrotCoord,
     pub symbols: Vec<FractalSymbol>,
@@ -23,11 +77,25 @@ pub struct FractalMind {
     pub inhibited_symbols: Vec<(usize, u32)>,  // (symbol_idx, steps_remaining) - refractory period
     pub inhibited_trajectories: Vec<(usize, u32)>,  // (traj_idx, steps_remaining)
     pub scale: u32,
-    pub exploration_radius: f64
+    #[serde(with = "crate::persist::json_float")]
+    pub exploration_radius: f64,
+    pub config: MindConfig,
+    pub coord_hasher: CoordHasherKind,  // Fixed for the mind's lifetime - changing it relocates every concept
+    #[serde(default)]
+    pub text_mapper: TextCoordMapper,  // Likewise fixed - hashed strings, or n-grams so similar spellings land close
+    pub rng: MindRng,  // Owned so runs can be replayed (see reseed)
+    #[serde(skip)]
+    pub spatial_index: SpatialIndex,  // Derived from symbols/trajectories, rebuilt on load
+    #[serde(skip)]
+    pub fingerprint_index: HammingIndex,  // Derived from symbol patterns, rebuilt on load
 }
 
 impl FractalMind {
     pub fn new() -> Self {
+        Self::with_config(MindConfig::default())
+    }
+
+    pub fn with_config(config: MindConfig) -> Self {
         println!("initializing fractal mind - mandelbrot indexes julia patterns...");
         FractalMind {
             current_coord: MandelbrotCoord::new(-0.5, 0.0),
@@ -43,9 +111,33 @@ impl FractalMind {
             background_thought_coord: None,
             scale: 4096,  // Lower scale = faster, more diversity
             exploration_radius: 0.20,
+            spatial_index: SpatialIndex::new(),
+            fingerprint_index: HammingIndex::new(config.merge_threshold),
+            config,
+            coord_hasher: CoordHasherKind::default(),
+            text_mapper: TextCoordMapper::default(),
+            rng: MindRng::from_entropy(),
         }
     }
 
+    /// Restart the random stream - same seed + same inputs + same state gives the same outputs
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = MindRng::from_seed(seed);
+    }
+
+    /// Swap hyperparameters (e.g. from a config file) on a trained mind
+    pub fn set_config(&mut self, config: MindConfig) {
+        self.config = config;
+        self.rebuild_indexes();
+    }
+
+    /// Re-sync the derived indexes after trajectories or symbols were replaced wholesale
+    pub fn rebuild_indexes(&mut self) {
+        self.spatial_index.rebuild(&self.trajectories, &self.symbols);
+        self.fingerprint_index = HammingIndex::new(self.config.merge_threshold);
+        self.fingerprint_index.rebuild(&self.symbols);
+    }
+
     pub fn process_input(&mut self, input: &str) -> String {
         self.process_with_intensity(input, 1.0)
     }
@@ -68,10 +160,11 @@ impl FractalMind {
 
     fn process_hierarchical(&mut self, input: &str, base_intensity: f64) {
         // Only update context for high-intensity inputs
-        if base_intensity > 0.3 {
+        if base_intensity > self.config.context_min_intensity {
             self.context_history.push(input.to_string());
-            if self.context_history.len() > 10 {
-                self.context_history.drain(0..1);
+            if self.context_history.len() > self.config.context_length {
+                let excess = self.context_history.len() - self.config.context_length;
+                self.context_history.drain(0..excess);
             }
         }
 
@@ -79,13 +172,14 @@ impl FractalMind {
 
         // Level 3: Full semantic chunks (sentences/paragraphs) - HIGHEST intensity
         // This is the primary conceptual understanding
-        let chunk_coord = hash_to_mandelbrot_coord(input);
-        let contextual_position = contextual_mandelbrot_coord(chunk_coord, &context, 0.7 * base_intensity);
+        let chunk_coord = self.text_coord(input);
+        let contextual_position = contextual_mandelbrot_coord_from(
+            chunk_coord, self.text_coord(&context), self.config.context_influence * base_intensity);
 
         // CRITICAL: Save contextual position BEFORE learning overwrites current_coord
         self.contextual_coord = contextual_position;
 
-        self.learn_concept_with_intensity(input, base_intensity * 0.8);
+        self.learn_concept_with_intensity(input, base_intensity * self.config.chunk_intensity);
 
         // Level 2: Word-level processing - MEDIUM intensity
         // Break into words for compositional understanding
@@ -95,54 +189,181 @@ impl FractalMind {
 
         if words.len() > 1 {
             for word in &words {
-                self.learn_concept_with_intensity(word, base_intensity * 0.4);
+                self.learn_concept_with_intensity(word, base_intensity * self.config.word_intensity);
             }
         }
 
         // Level 1: Character-level patterns - LOW intensity
         // Only for building basic pattern recognition, not primary storage
         // Process as continuous stream for pattern detection
-        if base_intensity > 0.5 {
+        if base_intensity > self.config.char_min_intensity {
             // Only do character-level for high-intensity learning
             let chars: String = input.chars()
                 .filter(|c| !c.is_whitespace())
-                .take(50) // Limit to prevent explosion
+                .take(self.config.char_stream_limit) // Limit to prevent explosion
                 .collect();
 
             if !chars.is_empty() {
-                self.learn_concept_with_intensity(&chars, base_intensity * 0.1);
+                self.learn_concept_with_intensity(&chars, base_intensity * self.config.char_intensity);
             }
         }
     }
 
+    /// Learn an ordered sequence (video frames, audio windows, ...).
+    /// Every step is processed like a normal input, then one temporal trajectory runs through
+    /// the steps' coordinates in order, so the sequence itself becomes a recallable path.
+    pub fn learn_sequence(&mut self, steps: &[String], intensity: f64) {
+        for step in steps {
+            self.process_hierarchical(step, intensity);
+            self.decay_symbols();
+        }
+
+        // Symbols are stored only after all decay so their indices stay valid
+        let mut path = Vec::new();
+        let mut symbol_indices = Vec::new();
+        for step in steps {
+            let coord = self.text_coord(step);
+            let label = step.chars().find(|c| !c.is_whitespace()).unwrap_or('·');
+            symbol_indices.push(self.store_symbol_at_coord(coord, label));
+            path.push(coord);
+        }
+
+        if path.len() < 2 {
+            return;
+        }
+
+        let trajectory = ConceptTrajectory::new(path, String::new(), symbol_indices);
+        self.spatial_index.insert_trajectory(self.trajectories.len(), &trajectory);
+        self.trajectories.push(trajectory);
+    }
+
     fn learn_concept(&mut self, concept: &str) {
         self.learn_concept_with_intensity(concept, 1.0)
     }
 
     pub fn learn_concept_with_image(&mut self, concept: &str, intensity: f64, image_path: String) {
-        self.learn_concept_with_intensity_and_image(concept, intensity, Some(image_path));
+        self.learn_concept_with_intensity_and_image(concept, intensity, Some(image_path), intensity < 0.2);
+    }
+
+    /// Learn one percept seen through several encodings (e.g. an image's edges, colors and
+    /// texture). Each channel becomes its own concept-level trajectory, carrying image_path,
+    /// plus a one-point anchor at channel_anchor so recall_images_by_channel finds it from
+    /// a similar channel string. One associative field links them all, so recall through any
+    /// channel reaches the rest.
+    pub fn learn_channels(&mut self, channels: &[String], intensity: f64, image_path: Option<String>) {
+        let mut members = Vec::new();
+        for channel in channels.iter().filter(|c| !c.is_empty()) {
+            members.push(self.trajectories.len());
+            self.learn_concept_with_intensity_and_image(channel, intensity, image_path.clone(), false);
+            self.decay_symbols();
+
+            let label = channel.chars().next().unwrap_or('·');
+            members.push(self.trajectories.len());
+            self.store_point_trajectory(self.channel_anchor(channel), label, channel.clone(), intensity, image_path.clone());
+        }
+
+        if members.len() < 2 {
+            return;
+        }
+
+        // Anchored where the first channel starts, so it is found from that channel's path
+        let anchor = self.trajectories[members[0]].path[0];
+        self.associative_fields.push(AssociativeField::linking(anchor, self.exploration_radius, members));
+    }
+
+    /// Process a numeric percept. Strings are hashed, which scatters near-identical
+    /// stimuli; a feature vector is projected instead, so similar ones land close together.
+    pub fn process_sensory(&mut self, vector: &SensoryVector, intensity: f64) -> String {
+        self.learn_sensory(vector, intensity, None);
+
+        if intensity < 0.3 {
+            self.background_thought_coord = Some(self.current_coord);
+        }
+        self.decay_symbols();
+
+        self.generate_response(vector.modality)
+    }
+
+    /// Learn a numeric percept as a one-point trajectory at its projected coordinate
+    pub fn learn_sensory(&mut self, vector: &SensoryVector, intensity: f64, image_path: Option<String>) {
+        let coord = sensory_to_mandelbrot_coord(vector);
+        let label = vector.modality.chars().next().unwrap_or('·');
+
+        // Responses start from the percept itself - there is no string context to blend in
+        self.current_coord = coord;
+        self.contextual_coord = coord;
+
+        self.store_point_trajectory(coord, label, vector.modality.to_string(), intensity, image_path);
+    }
+
+    fn store_point_trajectory(&mut self, coord: MandelbrotCoord, label: char, concept: String, intensity: f64, image_path: Option<String>) {
+        let symbol_idx = self.store_symbol_at_coord(coord, label);
+        let trajectory = match image_path {
+            Some(img_path) => ConceptTrajectory::new_with_image(vec![coord], concept, vec![symbol_idx], img_path),
+            None => ConceptTrajectory::new(vec![coord], concept, vec![symbol_idx]),
+        };
+        self.store_trajectory(trajectory, intensity);
+    }
+
+    /// Trajectories starting inside a field plus whatever it links, wherever they lie -
+    /// each once, so a member that also starts inside isn't weighted twice
+    fn field_trajectories(&self, field: &AssociativeField) -> Vec<usize> {
+        let mut in_field = self.spatial_index.trajectories_starting_within(field.center, field.radius);
+        in_field.extend(field.members.iter().copied().filter(|&m| m < self.trajectories.len()));
+        in_field.sort_unstable();
+        in_field.dedup();
+        in_field
+    }
+
+    /// Trajectories linked to traj_idx through associative fields (not including itself)
+    pub fn linked_trajectories(&self, traj_idx: usize) -> Vec<usize> {
+        let mut linked: Vec<usize> = self.associative_fields.iter()
+            .filter(|field| field.members.contains(&traj_idx))
+            .flat_map(|field| field.members.iter().copied())
+            .filter(|&member| member != traj_idx && member < self.trajectories.len())
+            .collect();
+        linked.sort_unstable();
+        linked.dedup();
+        linked
     }
 
     fn learn_concept_with_intensity(&mut self, concept: &str, intensity: f64) {
-        self.learn_concept_with_intensity_and_image(concept, intensity, None);
+        self.learn_concept_with_intensity_and_image(concept, intensity, None, intensity < 0.2);
+    }
+
+    /// Where a string lands, per this mind's hasher and text mapper
+    pub fn text_coord(&self, input: &str) -> MandelbrotCoord {
+        self.text_mapper.coord(&self.coord_hasher, input)
+    }
+
+    /// Where character i of a concept lands
+    fn concept_char_coord(&self, concept: &str, i: usize, ch: char, char_level: bool) -> MandelbrotCoord {
+        // Hash based on character + context, but allow merging of similar patterns
+        // For low-intensity (character-level), use simpler hash for consolidation
+        // For high-intensity (word/concept-level), include position for diversity
+        if char_level {
+            // Character-level: just the character itself for maximum merging
+            self.text_coord(&ch.to_string())
+        } else {
+            // Word/concept level: include position and context for diversity
+            let char_seed = format!("{}:{}:{}", ch, i, concept);
+
+            // The seed is mostly the concept, so n-grams would pile all its characters
+            // onto one spot - it is only a key, and gets hashed instead
+            let mapper = match self.text_mapper {
+                TextCoordMapper::NGram => TextCoordMapper::Hashed,
+                mapper => mapper,
+            };
+            mapper.coord(&self.coord_hasher, &char_seed)
+        }
     }
 
-    fn learn_concept_with_intensity_and_image(&mut self, concept: &str, intensity: f64, image_path: Option<String>) {
+    fn learn_concept_with_intensity_and_image(&mut self, concept: &str, intensity: f64, image_path: Option<String>, char_level: bool) {
         let mut path = Vec::new();
         let mut symbol_indices = Vec::new();
 
         for (i, ch) in concept.chars().enumerate() {
-            // Hash based on character + context, but allow merging of similar patterns
-            // For low-intensity (character-level), use simpler hash for consolidation
-            // For high-intensity (word/concept-level), include position for diversity
-            let char_coord = if intensity < 0.2 {
-                // Character-level: just the character itself for maximum merging
-                hash_to_mandelbrot_coord(&ch.to_string())
-            } else {
-                // Word/concept level: include position and context for diversity
-                let char_seed = format!("{}:{}:{}", ch, i, concept);
-                hash_to_mandelbrot_coord(&char_seed)
-            };
+            let char_coord = self.concept_char_coord(concept, i, ch, char_level);
 
             path.push(char_coord);
 
@@ -155,86 +376,82 @@ impl FractalMind {
 
         if !path.is_empty() {
             let trajectory = if let Some(img_path) = image_path {
-                ConceptTrajectory::new_with_image(path.clone(), concept.to_string(), symbol_indices.clone(), img_path)
+                ConceptTrajectory::new_with_image(path, concept.to_string(), symbol_indices, img_path)
             } else {
-                ConceptTrajectory::new(path.clone(), concept.to_string(), symbol_indices.clone())
+                ConceptTrajectory::new(path, concept.to_string(), symbol_indices)
             };
-            self.trajectories.push(trajectory);
-
-
-            // Add to symbol history for generation context
-            self.symbol_history.extend(symbol_indices.iter());
-            if self.symbol_history.len() > 20 {
-                self.symbol_history.drain(..self.symbol_history.len() - 20);
-            }
+            self.store_trajectory(trajectory, intensity);
+        }
+    }
 
-            // Create or strengthen associative field (scaled by intensity)
-            let mut found_field = false;
-            for field in &mut self.associative_fields {
-                if field.contains(self.current_coord) {
-                    field.strength += 0.1 * intensity;
-                    found_field = true;
-                    break;
+    /// Index and keep a new trajectory, strengthening what it overlaps and the field
+    /// around current_coord (scaled by intensity)
+    fn store_trajectory(&mut self, trajectory: ConceptTrajectory, intensity: f64) {
+        // Find overlapping trajectories before the new one is indexed
+        let mut overlapping = vec![false; self.trajectories.len()];
+        for &new_coord in &trajectory.path {
+            let nearby = self.spatial_index.nearby_trajectories(
+                &self.trajectories, new_coord, self.exploration_radius * 0.5);
+            for (traj_idx, _, dist) in nearby {
+                if dist < self.exploration_radius * 0.5 {
+                    overlapping[traj_idx] = true;
                 }
             }
+        }
 
-            if !found_field && intensity > 0.3 {
-                let field = AssociativeField::new(self.current_coord, self.exploration_radius);
-                self.associative_fields.push(field);
+        // Add to symbol history for generation context
+        self.symbol_history.extend(trajectory.symbols.iter());
+        if self.symbol_history.len() > 20 {
+            self.symbol_history.drain(..self.symbol_history.len() - 20);
+        }
+
+        self.spatial_index.insert_trajectory(self.trajectories.len(), &trajectory);
+        self.trajectories.push(trajectory);
+
+        // Create or strengthen associative field (scaled by intensity)
+        let mut found_field = false;
+        for field in &mut self.associative_fields {
+            if field.contains(self.current_coord) {
+                field.strength += 0.1 * intensity;
+                found_field = true;
+                break;
             }
+        }
 
-            // Strengthen connections between overlapping trajectories
-            let traj_count = self.trajectories.len() - 1;
-            for traj_idx in 0..traj_count {
-                let mut strengthen = false;
-                for &new_coord in &path {
-                    for &old_coord in &self.trajectories[traj_idx].path {
-                        let dist = ConceptTrajectory::coord_distance(new_coord, old_coord);
-                        if dist < self.exploration_radius * 0.5 {
-                            strengthen = true;
-                            break;
-                        }
-                    }
-                    if strengthen { break; }
-                }
-                if strengthen {
-                    self.trajectories[traj_idx].strength += 0.05;
-                }
+        if !found_field && intensity > 0.3 {
+            let field = AssociativeField::new(self.current_coord, self.exploration_radius);
+            self.associative_fields.push(field);
+        }
+
+        // Strengthen connections between overlapping trajectories
+        for (traj_idx, strengthen) in overlapping.into_iter().enumerate() {
+            if strengthen {
+                self.trajectories[traj_idx].strength += 0.05;
             }
         }
     }
 
     fn store_symbol_at_coord(&mut self, coord: MandelbrotCoord, ch: char) -> usize {
-        const MERGE_THRESHOLD: u32 = 8;
-
         let pattern = julia_fingerprint_from_mandelbrot(coord, self.scale);
         let stability = mandelbrot_stability(coord, self.scale);
 
-        let mut min_distance = u32::MAX;
-        let mut closest_symbol = None;
-
-        for (i, symbol) in self.symbols.iter_mut().enumerate() {
-            let distance = hamming_distance(&pattern, &symbol.pattern);
-            if distance < min_distance {
-                min_distance = distance;
-                closest_symbol = Some(i);
-            }
-
-            // Merge similar patterns - character label is just metadata
-            if distance < MERGE_THRESHOLD {
-                symbol.count += 1;
-                symbol.confidence = (symbol.confidence + 0.15).min(1.0);
-                // Keep first label or update if unlabeled
-                if symbol.label.is_none() {
-                    symbol.label = Some(ch);
-                }
-                return i;
+        // Merge similar patterns - character label is just metadata
+        if let Some(i) = self.fingerprint_index.first_within(&self.symbols, &pattern, self.config.merge_threshold) {
+            let symbol = &mut self.symbols[i];
+            symbol.count += 1;
+            symbol.confidence = (symbol.confidence + self.config.confidence_boost).min(1.0);
+            // Keep first label or update if unlabeled
+            if symbol.label.is_none() {
+                symbol.label = Some(ch);
             }
+            return i;
         }
 
         let new_symbol = FractalSymbol::new(coord, pattern, stability);
         self.symbols.push(new_symbol);
         let idx = self.symbols.len() - 1;
+        self.spatial_index.insert_symbol(idx, coord);
+        self.fingerprint_index.insert(idx, &self.symbols[idx].pattern);
         self.symbols[idx].label = Some(ch);
         idx
     }
@@ -255,9 +472,7 @@ impl FractalMind {
         // This is the PRIMARY driver - response emerges from input context
         let mut current_coord = self.contextual_coord;
 
-        let max_symbols = 100; // Generate up to 100 characters
-
-        let mut rng = rand::thread_rng();
+        let max_symbols = self.config.max_response_symbols;
 
         let mut used_trajectories: Vec<usize> = Vec::new(); // Track which trajectories we use (Hebbian)
         let mut last_traj_idx: Option<usize> = None; // Track trajectory to enforce forward movement
@@ -274,40 +489,48 @@ impl FractalMind {
             self.inhibited_trajectories.retain(|(_, steps)| *steps > 0);
             let mut candidates: Vec<(usize, &ConceptTrajectory, usize, f64)> = Vec::new();
 
-            // Search coordinate calculation:
-            // 85% - stay at current position (follow input context)
-            // 10% - integrate background thought
-            // 5%  - slight random exploration
-            let rand_val = rng.r#gen::<f64>();
+            // Search coordinate calculation (defaults 85/10/5):
+            // context_search_weight    - stay at current position (follow input context)
+            // background_search_weight - integrate background thought
+            // remainder                - slight random exploration
+            let rand_val = self.rng.r#gen::<f64>();
+            let blend = self.config.background_blend;
 
-            let search_coord = if rand_val < 0.85 {
+            let search_coord = if rand_val < self.config.context_search_weight {
                 // PRIMARY: Follow the input context trajectory
                 current_coord
-            } else if rand_val < 0.95 {
+            } else if rand_val < self.config.context_search_weight + self.config.background_search_weight {
                 // SECONDARY: Background thought influence
                 if let Some(bg_coord) = self.background_thought_coord {
                     // Blend background thought with current position
                     MandelbrotCoord {
-                        re: current_coord.re * 0.7 + bg_coord.re * 0.3,
-                        im: current_coord.im * 0.7 + bg_coord.im * 0.3,
+                        re: current_coord.re * (1.0 - blend) + bg_coord.re * blend,
+                        im: current_coord.im * (1.0 - blend) + bg_coord.im * blend,
                     }
                 } else {
                     current_coord
                 }
             } else {
                 // TERTIARY: Tiny bit of exploration
-                let nearby = find_nearby_interesting_points(current_coord, 0.1, 3);
+                let nearby = find_nearby_interesting_points(
+                    current_coord, self.config.explore_radius, self.config.explore_samples);
                 nearby.get(0).copied().unwrap_or(current_coord)
             };
 
-            for (traj_idx, trajectory) in self.trajectories.iter().enumerate() {
+            // Wide search - exploration_radius * search_radius_factor, capped where influence
+            // becomes negligible (the uncapped default spans the whole region)
+            let search_radius = (self.exploration_radius * self.config.search_radius_factor)
+                .min(ConceptTrajectory::influence_radius());
+            let nearby = self.spatial_index.nearby_trajectories(&self.trajectories, search_coord, search_radius);
+
+            for (traj_idx, closest_idx, dist) in nearby {
                 // INHIBITION: Skip recently-used trajectories (refractory period)
                 if self.inhibited_trajectories.iter().any(|(idx, _)| *idx == traj_idx) {
                     continue;
                 }
 
-                let (closest_idx, dist) = trajectory.closest_point(search_coord);
-                let influence = trajectory.influence_at(search_coord);
+                let trajectory = &self.trajectories[traj_idx];
+                let influence = trajectory.influence_at_distance(dist);
 
                 // FORWARD MOVEMENT: If continuing same trajectory, must move forward
                 let next_idx = if Some(traj_idx) == last_traj_idx {
@@ -318,8 +541,7 @@ impl FractalMind {
                     closest_idx
                 };
 
-                // Wide search - exploration_radius * 10
-                if dist < self.exploration_radius * 10.0 && next_idx < trajectory.symbols.len() {
+                if dist < search_radius && next_idx < trajectory.symbols.len() {
                     let symbol_idx = trajectory.symbols[next_idx];
 
                     // INHIBITION: Skip recently-used symbols
@@ -331,7 +553,11 @@ impl FractalMind {
                     let familiarity = trajectory.strength;
 
                     // Pattern richness: prefer multi-character trajectories
-                    let pattern_bonus = if trajectory.symbols.len() > 5 { 2.0 } else { 0.5 };
+                    let pattern_bonus = if trajectory.symbols.len() > self.config.long_pattern_symbols {
+                        self.config.long_pattern_bonus
+                    } else {
+                        self.config.short_pattern_bonus
+                    };
 
                     let weight = influence * familiarity * pattern_bonus;
                     candidates.push((traj_idx, trajectory, symbol_idx, weight));
@@ -341,11 +567,11 @@ impl FractalMind {
             // Also check associative fields for distant but semantically related patterns
             for field in &self.associative_fields {
                 let field_dist = ConceptTrajectory::coord_distance(search_coord, field.center);
-                if field_dist < field.radius * 3.0 {
-                    // Find trajectories in this field
-                    for (traj_idx, trajectory) in self.trajectories.iter().enumerate() {
-                        if field.contains(trajectory.path[0]) && !trajectory.symbols.is_empty() {
-                            let field_influence = field.strength * 0.5;
+                if field_dist < field.radius * self.config.field_reach {
+                    for traj_idx in self.field_trajectories(field) {
+                        let trajectory = &self.trajectories[traj_idx];
+                        if !trajectory.symbols.is_empty() {
+                            let field_influence = field.strength * self.config.field_influence;
                             let symbol_idx = trajectory.symbols[0];
                             candidates.push((traj_idx, trajectory, symbol_idx, field_influence));
                         }
@@ -366,7 +592,7 @@ impl FractalMind {
                 break;
             }
 
-            let mut r = rng.gen_range(0.0..total);
+            let mut r = self.rng.gen_range(0.0..total);
 
             let mut selected = None;
             for (traj_idx, traj, symbol_idx, weight) in &candidates {
@@ -381,9 +607,9 @@ impl FractalMind {
                 // HEBBIAN STRENGTHENING: Reinforce used trajectory
                 used_trajectories.push(traj_idx);
 
-                // INHIBITION: Add to refractory period (3-5 steps)
-                self.inhibited_symbols.push((symbol_idx, 3));
-                self.inhibited_trajectories.push((traj_idx, 2));
+                // INHIBITION: Add to refractory period
+                self.inhibited_symbols.push((symbol_idx, self.config.symbol_inhibition));
+                self.inhibited_trajectories.push((traj_idx, self.config.trajectory_inhibition));
 
                 // Track which trajectory we're following
                 last_traj_idx = Some(traj_idx);
@@ -414,13 +640,13 @@ impl FractalMind {
         // HEBBIAN STRENGTHENING: Reinforce trajectories that were used
         for traj_idx in used_trajectories {
             if traj_idx < self.trajectories.len() {
-                self.trajectories[traj_idx].strength += 0.1;
+                self.trajectories[traj_idx].strength += self.config.hebbian_boost;
             }
         }
 
         // TRAJECTORY DECAY: Weaken all trajectories slightly over time
         for traj in &mut self.trajectories {
-            traj.strength = (traj.strength * 0.995).max(0.1); // Decay to minimum 0.1
+            traj.strength = (traj.strength * self.config.trajectory_decay).max(self.config.min_trajectory_strength);
         }
 
         let output = if response.is_empty() {
@@ -445,49 +671,55 @@ impl FractalMind {
         // - NO vocabulary lookup, only geometric patterns
 
         let mut thought = String::new();
-        let mut rng = rand::thread_rng();
 
         // Start from current position but with some drift
-        let drift_amount = 0.3;
+        let drift_amount = self.config.thought_drift;
         let mut current_coord = MandelbrotCoord {
-            re: self.current_coord.re + (rng.r#gen::<f64>() - 0.5) * drift_amount,
-            im: self.current_coord.im + (rng.r#gen::<f64>() - 0.5) * drift_amount,
+            re: self.current_coord.re + (self.rng.r#gen::<f64>() - 0.5) * drift_amount,
+            im: self.current_coord.im + (self.rng.r#gen::<f64>() - 0.5) * drift_amount,
         };
 
-        let max_symbols = 30; // Shorter background thoughts
+        let max_symbols = self.config.max_thought_symbols; // Shorter background thoughts
 
         for _step in 0..max_symbols {
             let mut candidates: Vec<(&ConceptTrajectory, usize, f64)> = Vec::new();
 
-            // Background thought search weighting:
-            // 50% - current wandering position
-            // 30% - nearby associations
-            // 20% - random exploration
-            let rand_val = rng.r#gen::<f64>();
+            // Background thought search weighting (defaults 50/30/20):
+            // thought_current_weight - current wandering position
+            // thought_nearby_weight  - nearby associations
+            // remainder              - random exploration
+            let rand_val = self.rng.r#gen::<f64>();
 
-            let search_coord = if rand_val < 0.5 {
+            let search_coord = if rand_val < self.config.thought_current_weight {
                 current_coord
-            } else if rand_val < 0.8 {
+            } else if rand_val < self.config.thought_current_weight + self.config.thought_nearby_weight {
                 // Explore nearby associations
-                let nearby = find_nearby_interesting_points(current_coord, 0.3, 5);
+                let nearby = find_nearby_interesting_points(
+                    current_coord, self.config.thought_nearby_radius, self.config.thought_nearby_samples);
                 nearby.get(0).copied().unwrap_or(current_coord)
             } else {
                 // Random exploration
-                let nearby = find_nearby_interesting_points(current_coord, 0.5, 10);
+                let nearby = find_nearby_interesting_points(
+                    current_coord, self.config.thought_explore_radius, self.config.thought_explore_samples);
                 if nearby.is_empty() {
                     current_coord
                 } else {
-                    nearby.get(rng.gen_range(0..nearby.len())).copied().unwrap_or(current_coord)
+                    nearby.get(self.rng.gen_range(0..nearby.len())).copied().unwrap_or(current_coord)
                 }
             };
 
             // Find candidate patterns
-            for trajectory in &self.trajectories {
-                let (closest_idx, dist) = trajectory.closest_point(search_coord);
+            // Wider search for background thoughts (more exploratory), capped where influence
+            // becomes negligible
+            let search_radius = (self.exploration_radius * self.config.thought_radius_factor)
+                .min(ConceptTrajectory::influence_radius());
+            let nearby = self.spatial_index.nearby_trajectories(&self.trajectories, search_coord, search_radius);
+
+            for (traj_idx, closest_idx, dist) in nearby {
+                let trajectory = &self.trajectories[traj_idx];
 
-                // Wider search for background thoughts (more exploratory)
-                if dist < self.exploration_radius * 15.0 && closest_idx < trajectory.symbols.len() {
-                    let influence = trajectory.influence_at(search_coord);
+                if dist < search_radius && closest_idx < trajectory.symbols.len() {
+                    let influence = trajectory.influence_at_distance(dist);
                     let symbol_idx = trajectory.symbols[closest_idx];
                     candidates.push((trajectory, symbol_idx, influence * trajectory.strength));
                 }
@@ -503,7 +735,7 @@ impl FractalMind {
                 break;
             }
 
-            let mut r = rng.gen_range(0.0..total);
+            let mut r = self.rng.gen_range(0.0..total);
             let mut selected = None;
 
             for (traj, symbol_idx, weight) in &candidates {
@@ -528,8 +760,8 @@ impl FractalMind {
                 // Wander along trajectory with some drift
                 if let Some((next_coord, _)) = traj.suggest_next_coord(current_coord) {
                     current_coord = MandelbrotCoord {
-                        re: next_coord.re + (rng.r#gen::<f64>() - 0.5) * 0.1,
-                        im: next_coord.im + (rng.r#gen::<f64>() - 0.5) * 0.1,
+                        re: next_coord.re + (self.rng.r#gen::<f64>() - 0.5) * self.config.thought_wander,
+                        im: next_coord.im + (self.rng.r#gen::<f64>() - 0.5) * self.config.thought_wander,
                     };
                 }
             } else {
@@ -545,7 +777,8 @@ impl FractalMind {
 
     fn explore_response(&mut self) -> String {
         // When no patterns match, explore nearby space
-        let nearby_points = find_nearby_interesting_points(self.current_coord, 0.1, 30);
+        let nearby_points = find_nearby_interesting_points(
+            self.current_coord, self.config.fallback_radius, self.config.fallback_samples);
 
         for point in nearby_points {
             if let Some(symbol_idx) = self.find_nearest_symbol(point) {
@@ -581,27 +814,17 @@ impl FractalMind {
     }
 
     fn find_nearest_symbol(&self, coord: MandelbrotCoord) -> Option<usize> {
-        let mut best_idx = None;
-        let mut best_dist = f64::INFINITY;
-
-        for (i, symbol) in self.symbols.iter().enumerate() {
-            let dist = ConceptTrajectory::coord_distance(coord, symbol.coord);
-            if dist < best_dist {
-                best_dist = dist;
-                best_idx = Some(i);
-            }
-        }
-
-        best_idx
+        self.spatial_index.nearest_symbol(&self.symbols, coord)
     }
 
     fn decay_symbols(&mut self) {
         // Gradually decay confidence of all symbols (synaptic pruning)
-        const DECAY_RATE: f32 = 0.001;
-        const MIN_CONFIDENCE: f32 = 0.05;
+        let decay_rate = self.config.decay_rate;
+        let min_confidence = self.config.min_confidence;
+        let prune_min_count = self.config.prune_min_count;
 
         for symbol in &mut self.symbols {
-            symbol.confidence = (symbol.confidence - DECAY_RATE).max(0.0);
+            symbol.confidence = (symbol.confidence - decay_rate).max(0.0);
         }
 
         // Mark symbols that are referenced by trajectories (can't be pruned)
@@ -619,7 +842,7 @@ impl FractalMind {
         let mut index_map = vec![0; self.symbols.len()];
 
         for (old_idx, symbol) in self.symbols.iter().enumerate() {
-            if symbol.confidence > MIN_CONFIDENCE || symbol.count > 10 || referenced[old_idx] {
+            if symbol.confidence > min_confidence || symbol.count > prune_min_count || referenced[old_idx] {
                 index_map[old_idx] = new_symbols.len();
                 new_symbols.push(symbol.clone());
             }
@@ -643,7 +866,125 @@ impl FractalMind {
                 .collect();
         }
 
+        let pruned = new_symbols.len() != self.symbols.len();
         self.symbols = new_symbols;
+
+        // Symbol indices shifted - keep the indexes in sync
+        if pruned {
+            self.spatial_index.rebuild_symbols(&self.symbols);
+            self.fingerprint_index.rebuild(&self.symbols);
+        }
+    }
+
+    /// Fold an independently trained mind into this one (strengths combined with StrengthPolicy::Max)
+    pub fn merge(&mut self, other: FractalMind) -> Result<(), MergeError> {
+        self.merge_with(other, StrengthPolicy::Max)
+    }
+
+    /// Refuses (leaving this mind untouched) a mind that maps text differently
+    pub fn merge_with(&mut self, other: FractalMind, policy: StrengthPolicy) -> Result<(), MergeError> {
+        if other.text_mapper != self.text_mapper || other.coord_hasher != self.coord_hasher {
+            return Err(MergeError::TextMapping {
+                ours: (self.text_mapper, self.coord_hasher),
+                theirs: (other.text_mapper, other.coord_hasher),
+            });
+        }
+
+        // Symbols: same dedupe rule as store_symbol_at_coord
+        let mut symbol_map = Vec::with_capacity(other.symbols.len());
+        let mut new_symbols = 0;
+
+        for mut symbol in other.symbols {
+            // Fingerprints are only comparable at the same scale
+            if other.scale != self.scale {
+                symbol.pattern = julia_fingerprint_from_mandelbrot(symbol.coord, self.scale);
+                symbol.stability = mandelbrot_stability(symbol.coord, self.scale);
+            }
+
+            if let Some(i) = self.fingerprint_index.first_within(&self.symbols, &symbol.pattern, self.config.merge_threshold) {
+                let existing = &mut self.symbols[i];
+                existing.count += symbol.count;
+                existing.confidence = existing.confidence.max(symbol.confidence);
+                if existing.label.is_none() {
+                    existing.label = symbol.label;
+                }
+                symbol_map.push(i);
+            } else {
+                let idx = self.symbols.len();
+                self.fingerprint_index.insert(idx, &symbol.pattern);
+                self.symbols.push(symbol);
+                symbol_map.push(idx);
+                new_symbols += 1;
+            }
+        }
+
+        // Trajectories: remap symbol indices, combine strengths of identical trajectories
+        let mut by_path: HashMap<Vec<(u64, u64)>, usize> = HashMap::new();
+        for (traj_idx, traj) in self.trajectories.iter().enumerate() {
+            by_path.entry(path_key(traj)).or_insert(traj_idx);
+        }
+
+        // Where each of other's trajectories ends up, for remapping field members
+        let mut trajectory_map = Vec::with_capacity(other.trajectories.len());
+        let mut new_trajectories = 0;
+        for mut traj in other.trajectories {
+            traj.symbols = traj.symbols.iter()
+                .filter_map(|&old_idx| symbol_map.get(old_idx).copied())
+                .collect();
+
+            let existing = by_path.get(&path_key(&traj)).copied()
+                .filter(|&i| self.trajectories[i].symbols == traj.symbols);
+
+            if let Some(i) = existing {
+                let strength = policy.combine(self.trajectories[i].strength, traj.strength);
+                self.trajectories[i].strength = strength;
+                if self.trajectories[i].image_path.is_none() {
+                    self.trajectories[i].image_path = traj.image_path;
+                }
+                trajectory_map.push(i);
+            } else {
+                by_path.entry(path_key(&traj)).or_insert(self.trajectories.len());
+                trajectory_map.push(self.trajectories.len());
+                self.trajectories.push(traj);
+                new_trajectories += 1;
+            }
+        }
+
+        // Associative fields: union fields whose centers fall within each other's radius
+        let mut new_fields = 0;
+        for mut field in other.associative_fields {
+            field.members = field.members.iter()
+                .filter_map(|&old_idx| trajectory_map.get(old_idx).copied())
+                .collect();
+            field.members.sort_unstable();
+
+            // Fields linking different percepts stay separate even when they overlap
+
+            let overlapping = self.associative_fields.iter_mut().find(|f| {
+                let dist = ConceptTrajectory::coord_distance(f.center, field.center);
+                dist <= f.radius && dist <= field.radius && f.members == field.members
+            });
+
+            if let Some(existing) = overlapping {
+                let dist = ConceptTrajectory::coord_distance(existing.center, field.center);
+                existing.radius = existing.radius.max(dist + field.radius);
+                existing.strength = policy.combine(existing.strength, field.strength);
+            } else {
+                self.associative_fields.push(field);
+                new_fields += 1;
+            }
+        }
+
+        // Symbol indices in inhibition/history no longer mean the same thing
+        self.inhibited_symbols.clear();
+        self.inhibited_trajectories.clear();
+        self.symbol_history.clear();
+
+        self.rebuild_indexes();
+
+        println!("merged {} new symbols, {} new trajectories, {} new fields",
+                 new_symbols, new_trajectories, new_fields);
+        Ok(())
     }
 
     pub fn display_state(&self) {
@@ -679,25 +1020,70 @@ impl FractalMind {
         // Find trajectories with image paths near this concept
         let mut candidates: Vec<(String, f64)> = Vec::new();
 
-        for traj in &self.trajectories {
-            if let Some(ref img_path) = traj.image_path {
-                let (_, dist) = traj.closest_point(current);
+        // Only trajectories close enough for relevance > 0.01 at the strongest strength matter
+        let max_strength = self.trajectories.iter().map(|t| t.strength).fold(0.0, f64::max);
+        let search_radius = (0.1 * (100.0 * max_strength).ln()).max(0.0).sqrt();
+        let nearby = self.spatial_index.nearby_trajectories(&self.trajectories, current, search_radius);
+
+        for (traj_idx, _, dist) in nearby {
+            let traj = &self.trajectories[traj_idx];
+            if let Some(img_path) = self.image_path_of(traj_idx) {
                 let relevance = traj.strength * (-dist * dist / 0.1).exp();
 
                 if relevance > 0.01 {
-                    candidates.push((img_path.clone(), relevance));
+                    candidates.push((img_path.to_string(), relevance));
                 }
             }
         }
 
-        // Sort by relevance
-        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        rank_image_paths(candidates, limit)
+    }
+
+    /// Recall images through one encoding channel (e.g. only the `colors:` string of a
+    /// new picture). Matches the anchors learn_channels stored for that channel by where
+    /// they sit, then follows associative links to the image they were learned with.
+    pub fn recall_images_by_channel(&self, encoded: &str, limit: usize) -> Vec<String> {
+        if encoded.is_empty() {
+            return Vec::new();
+        }
+        self.recall_images_starting_near(self.channel_anchor(encoded), limit)
+    }
+
+    /// Where a channel string is anchored: its projected channel_vector when it has one
+    /// (colors, histograms), else the n-gram coordinate, so similar strings land close
+    fn channel_anchor(&self, encoded: &str) -> MandelbrotCoord {
+        match channel_vector(encoded) {
+            Some(vector) => sensory_to_mandelbrot_coord(&vector),
+            None => ngram_to_mandelbrot_coord(encoded),
+        }
+    }
+
+    /// Recall images whose sensory vectors (see learn_sensory) were close to this one
+    pub fn recall_images_by_sensory(&self, vector: &SensoryVector, limit: usize) -> Vec<String> {
+        self.recall_images_starting_near(sensory_to_mandelbrot_coord(vector), limit)
+    }
+
+    fn recall_images_starting_near(&self, start: MandelbrotCoord, limit: usize) -> Vec<String> {
+        let radius = self.exploration_radius * 0.5;
+        let mut candidates: Vec<(String, f64)> = Vec::new();
+        for traj_idx in self.spatial_index.trajectories_starting_within(start, radius) {
+            let traj = &self.trajectories[traj_idx];
+            if let Some(img_path) = self.image_path_of(traj_idx) {
+                let dist = ConceptTrajectory::coord_distance(traj.path[0], start);
+                candidates.push((img_path.to_string(), traj.strength * (-dist * dist / 0.01).exp()));
+            }
+        }
+
+        rank_image_paths(candidates, limit)
+    }
 
-        // Return top N image paths
-        candidates.into_iter()
-            .take(limit)
-            .map(|(path, _)| path)
-            .collect()
+    /// The trajectory's own image, else the image of a trajectory linked to it
+    fn image_path_of(&self, traj_idx: usize) -> Option<&str> {
+        self.trajectories[traj_idx].image_path.as_deref().or_else(|| {
+            self.linked_trajectories(traj_idx)
+                .into_iter()
+                .find_map(|linked| self.trajectories[linked].image_path.as_deref())
+        })
     }
 
     /// Imagine/recall visual pattern from concept (generative)
@@ -714,13 +1100,16 @@ impl FractalMind {
                 // Find trajectories near current position
                 let mut next_coords: Vec<(MandelbrotCoord, usize, f64)> = Vec::new();
 
-                for trajectory in &self.trajectories {
-                    let (closest_idx, dist) = trajectory.closest_point(current_coord);
+                let nearby = self.spatial_index.nearby_trajectories(
+                    &self.trajectories, current_coord, self.exploration_radius);
+
+                for (traj_idx, closest_idx, dist) in nearby {
+                    let trajectory = &self.trajectories[traj_idx];
 
                     if dist < self.exploration_radius && closest_idx + 1 < trajectory.symbols.len() {
                         let next_coord = trajectory.path[closest_idx + 1];
                         let next_sym = trajectory.symbols[closest_idx + 1];
-                        let influence = trajectory.influence_at(current_coord);
+                        let influence = trajectory.influence_at_distance(dist);
 
                         next_coords.push((next_coord, next_sym, influence));
                     }
@@ -729,8 +1118,7 @@ impl FractalMind {
                 // Sample symbol from fractal space
                 if !next_coords.is_empty() {
                     let total: f64 = next_coords.iter().map(|(_, _, w)| w).sum();
-                    let mut rng = rand::thread_rng();
-                    let mut r = rng.gen_range(0.0..total.max(0.01));
+                    let mut r = self.rng.gen_range(0.0..total.max(0.01));
 
                     for &(coord, sym_idx, weight) in &next_coords {
                         if r < weight {
@@ -758,25 +1146,200 @@ impl FractalMind {
         visual
     }
 
-    pub fn save(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn save(&self, filename: &str) -> Result<(), MindStateError> {
+        self.save_with_snapshots(filename, persist::DEFAULT_SNAPSHOT_COUNT)
+    }
+
+    /// Atomic save that keeps up to `keep` rotating snapshots of previous states
+    pub fn save_with_snapshots(&self, filename: &str, keep: usize) -> Result<(), MindStateError> {
         println!("saving fractal mind...");
-        let cfg = bincode::config::standard();
-        let encoded = bincode::encode_to_vec(self, cfg)?;
-        fs::write(filename, encoded)?;
+        let encoded = persist::encode_mind(self)?;
+        let path = Path::new(filename);
+        persist::rotate_snapshots(path, keep)?;
+        persist::write_atomic(path, &encoded)?;
         println!("saved {} symbols, {} trajectories, {} fields",
                  self.symbols.len(), self.trajectories.len(), self.associative_fields.len());
         Ok(())
     }
 
-    pub fn load(filename: &str) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn load(filename: &str) -> Result<Self, MindStateError> {
         let data = fs::read(filename)?;
-        let cfg = bincode::config::standard();
-        let (mut mind, _len): (FractalMind, usize) = bincode::decode_from_slice(&data, cfg)?;
+        let mut mind = persist::decode_mind(&data)?;
         mind.context_history.clear();
         mind.symbol_history.clear();
+        mind.rebuild_indexes();
 
         println!("loaded {} symbols, {} trajectories, {} fields",
                  mind.symbols.len(), mind.trajectories.len(), mind.associative_fields.len());
         Ok(mind)
     }
-}
\ No newline at end of file
+
+    /// Write the full mind as pretty-printed JSON (fingerprints as hex)
+    pub fn export_json(&self, filename: &str) -> Result<(), MindStateError> {
+        let json = serde_json::to_vec_pretty(self)?;
+        persist::write_atomic(Path::new(filename), &json)?;
+        println!("exported {} symbols, {} trajectories, {} fields to {}",
+                 self.symbols.len(), self.trajectories.len(), self.associative_fields.len(), filename);
+        Ok(())
+    }
+
+    /// Read a mind written by export_json - encodes to the same bincode state it was exported from
+    pub fn import_json(filename: &str) -> Result<Self, MindStateError> {
+        let data = fs::read(filename)?;
+        let mut mind: FractalMind = serde_json::from_slice(&data)?;
+        mind.rebuild_indexes();
+
+        println!("imported {} symbols, {} trajectories, {} fields",
+                 mind.symbols.len(), mind.trajectories.len(), mind.associative_fields.len());
+        Ok(mind)
+    }
+}
+
+// Exact bit pattern of a trajectory's path, for spotting identical trajectories
+fn path_key(traj: &ConceptTrajectory) -> Vec<(u64, u64)> {
+    traj.path.iter().map(|c| (c.re.to_bits(), c.im.to_bits())).collect()
+}
+
+/// Image paths by descending relevance, each path once
+fn rank_image_paths(mut candidates: Vec<(String, f64)>, limit: usize) -> Vec<String> {
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let mut paths: Vec<String> = Vec::new();
+    for (path, _) in candidates {
+        if paths.len() == limit {
+            break;
+        }
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use crate::image::{encode_image_data, encode_image_data_multi, ImageEncoding};
+
+    /// A small seeded mind - the low scale keeps fingerprints cheap in debug builds
+    fn trained_mind(seed: u64) -> FractalMind {
+        let mut mind = FractalMind::new();
+        mind.scale = 256;
+        mind.reseed(seed);
+        for input in ["the cat sat", "a dog ran home", "the cat ran"] {
+            mind.process_input(input);
+        }
+        mind
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("fractal_mind_test_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn seeded_runs_replay_exactly() {
+        let inputs = ["hello there", "the cat sat", "where did the cat go", "hello"];
+        let run = |name: &str| {
+            let mut mind = trained_mind(42);
+            let outputs: Vec<String> = inputs.iter().map(|input| mind.process_input(input)).collect();
+            let path = temp_path(name);
+            mind.save_with_snapshots(path.to_str().unwrap(), 0).unwrap();
+            let bytes = fs::read(&path).unwrap();
+            fs::remove_file(&path).ok();
+            (outputs, bytes)
+        };
+
+        let (first_outputs, first_bytes) = run("replay_a.bin");
+        let (second_outputs, second_bytes) = run("replay_b.bin");
+        assert!(first_outputs.iter().any(|output| output != "?"), "the mind should answer something");
+        assert_eq!(first_outputs, second_outputs);
+        assert_eq!(first_bytes, second_bytes);
+    }
+
+    #[test]
+    fn json_round_trip_reproduces_bincode() {
+        let mut mind = trained_mind(7);
+        mind.trajectories[0].strength = f64::NAN;
+        mind.trajectories[1].strength = f64::INFINITY;
+        mind.symbols[0].confidence = f32::NEG_INFINITY;
+
+        let path = temp_path("round_trip.json");
+        mind.export_json(path.to_str().unwrap()).unwrap();
+        let imported = FractalMind::import_json(path.to_str().unwrap());
+        fs::remove_file(&path).ok();
+
+        assert_eq!(persist::encode_mind(&imported.unwrap()).unwrap(), persist::encode_mind(&mind).unwrap());
+    }
+
+    /// A 48x48 picture: mostly `main`, with a light background band
+    fn two_tone(main: [u8; 3]) -> ::image::DynamicImage {
+        ::image::DynamicImage::ImageRgb8(::image::RgbImage::from_fn(48, 48, |_, y| {
+            ::image::Rgb(if y < 34 { main } else { [240, 240, 240] })
+        }))
+    }
+
+    #[test]
+    fn color_channel_recalls_the_image_learned_with_it() {
+        let mut mind = trained_mind(11);
+        for (path, color) in [("red.png", [220, 30, 30]), ("blue.png", [30, 40, 210])] {
+            let channels: Vec<String> = encode_image_data_multi(&two_tone(color), &ImageEncoding::default_channels())
+                .into_iter()
+                .map(|channel| channel.encoded)
+                .collect();
+            mind.learn_channels(&channels, 0.3, Some(path.to_string()));
+        }
+
+        let colors = ImageEncoding::DominantColors { color_count: 5 };
+        let reddish = encode_image_data(&two_tone([205, 45, 35]), colors);
+        let bluish = encode_image_data(&two_tone([40, 55, 190]), colors);
+        assert_eq!(mind.recall_images_by_channel(&reddish, 2).first().map(String::as_str), Some("red.png"));
+        assert_eq!(mind.recall_images_by_channel(&bluish, 2).first().map(String::as_str), Some("blue.png"));
+    }
+
+    #[test]
+    fn ngram_mapper_keeps_concept_characters_apart() {
+        let mut mind = FractalMind::new();
+        mind.text_mapper = TextCoordMapper::NGram;
+        let concept = "the quick brown fox";
+        let coords: Vec<MandelbrotCoord> = concept.chars().enumerate()
+            .map(|(i, ch)| mind.concept_char_coord(concept, i, ch, false))
+            .collect();
+
+        for (i, a) in coords.iter().enumerate() {
+            for b in &coords[i + 1..] {
+                assert!(ConceptTrajectory::coord_distance(*a, *b) > 1e-3, "characters collapsed onto one point");
+            }
+        }
+    }
+
+    #[test]
+    fn merge_refuses_a_mind_that_maps_text_differently() {
+        let mut mind = trained_mind(5);
+        let before = persist::encode_mind(&mind).unwrap();
+
+        let mut other = trained_mind(6);
+        other.text_mapper = TextCoordMapper::NGram;
+        assert!(matches!(mind.merge(other), Err(MergeError::TextMapping { .. })));
+
+        let mut other = trained_mind(6);
+        other.coord_hasher = CoordHasherKind::Fnv1a;
+        assert!(matches!(mind.merge(other), Err(MergeError::TextMapping { .. })));
+        assert_eq!(persist::encode_mind(&mind).unwrap(), before, "a refused merge must not change the mind");
+
+        assert!(mind.merge(trained_mind(6)).is_ok());
+    }
+
+    #[test]
+    fn field_member_starting_inside_is_a_candidate_once() {
+        let mut mind = trained_mind(13);
+        mind.learn_channels(&["colors:rgb:200,30,30@100|".to_string(), "edges".to_string()], 0.3, None);
+        let field = mind.associative_fields.last().unwrap().clone();
+
+        let inside = mind.spatial_index.trajectories_starting_within(field.center, field.radius);
+        let member = *field.members.iter().find(|m| inside.contains(m)).expect("a member starts at the field centre");
+
+        let candidates = mind.field_trajectories(&field);
+        assert_eq!(candidates.iter().filter(|&&t| t == member).count(), 1);
+    }
+}
@@ -0,0 +1,287 @@
+// Onset and tempo detection for audio encoding
+// Spectral flux with an adaptive (moving median) threshold, band-energy
+// classification of each onset, and autocorrelation tempo estimation.
+
+use crate::spectral::SpectralFrontEnd;
+
+/// Half-width (in frames) of the moving median used as the onset threshold
+const THRESHOLD_RADIUS: usize = 8;
+/// Half-width (in frames) an onset must be the local maximum over
+const PEAK_RADIUS: usize = 3;
+/// Flux below this is numerical noise from steady or near-silent input
+const MIN_FLUX: f32 = 1.0;
+
+const KICK_MAX_HZ: f32 = 150.0;
+const HAT_MIN_HZ: f32 = 5000.0;
+
+/// Tempo search range and the prior that resolves double/half tempo ambiguity
+const MIN_BPM: f32 = 60.0;
+const MAX_BPM: f32 = 200.0;
+const PREFERRED_BPM: f32 = 120.0;
+
+/// What an onset most likely was, from where its new energy sits in the spectrum
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hit {
+    Kick,    // Mostly below 150Hz
+    Snare,   // Broadband noise through the mids
+    Hat,     // Noise concentrated above 5kHz
+    Tonal,   // Peaky spectrum - a note or chord rather than a drum
+}
+
+impl Hit {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Hit::Kick => "kick",
+            Hit::Snare => "snare",
+            Hit::Hat => "hat",
+            Hit::Tonal => "tonal",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Onset {
+    pub time: f32,       // Seconds from the start of the analysed samples
+    pub strength: f32,   // Spectral flux at the onset
+    pub hit: Hit,
+}
+
+pub struct OnsetAnalysis {
+    pub onsets: Vec<Onset>,
+    pub tempo: Option<f32>,   // BPM, None when the signal is too short or has no pulse
+}
+
+/// Detect and classify onsets and estimate the tempo of a block of samples
+pub fn analyze(samples: &[f32], front_end: &SpectralFrontEnd) -> OnsetAnalysis {
+    let spectrogram = front_end.spectrogram(samples);
+    let flux = spectral_flux(&spectrogram);
+    let frame_rate = front_end.sample_rate as f32 / front_end.hop as f32;
+
+    let onsets = pick_onsets(&flux)
+        .into_iter()
+        .map(|frame| Onset {
+            time: frame as f32 / frame_rate,
+            strength: flux[frame],
+            hit: classify(&spectrogram[frame.saturating_sub(1)], &spectrogram[frame], front_end),
+        })
+        .collect();
+
+    OnsetAnalysis { onsets, tempo: estimate_tempo(&flux, frame_rate) }
+}
+
+/// Rectified increase in log-compressed magnitude per frame (frame 0 is always 0)
+pub fn spectral_flux(spectrogram: &[Vec<f32>]) -> Vec<f32> {
+    let compress = |power: f32| (1.0 + 100.0 * power.sqrt()).ln();
+
+    let mut flux = vec![0.0; spectrogram.len()];
+    for t in 1..spectrogram.len() {
+        flux[t] = spectrogram[t].iter()
+            .zip(&spectrogram[t - 1])
+            .map(|(&now, &before)| (compress(now) - compress(before)).max(0.0))
+            .sum();
+    }
+    flux
+}
+
+/// Frames whose flux is a local maximum and clearly above the moving median
+pub fn pick_onsets(flux: &[f32]) -> Vec<usize> {
+    if flux.is_empty() {
+        return Vec::new();
+    }
+
+    let mean = flux.iter().sum::<f32>() / flux.len() as f32;
+    let delta = 0.5 * mean;
+
+    (1..flux.len())
+        .filter(|&t| {
+            let lo = t.saturating_sub(THRESHOLD_RADIUS);
+            let hi = (t + THRESHOLD_RADIUS + 1).min(flux.len());
+            let mut neighbourhood = flux[lo..hi].to_vec();
+            neighbourhood.sort_by(f32::total_cmp);
+            let threshold = neighbourhood[neighbourhood.len() / 2] * 1.5 + delta;
+
+            let peak_lo = t.saturating_sub(PEAK_RADIUS);
+            let peak_hi = (t + PEAK_RADIUS + 1).min(flux.len());
+            let is_peak = flux[peak_lo..peak_hi].iter().enumerate()
+                .all(|(i, &f)| f < flux[t] || (f == flux[t] && peak_lo + i >= t));
+
+            is_peak && flux[t] > threshold.max(MIN_FLUX)
+        })
+        .collect()
+}
+
+/// Classify an onset from the energy it added between two power spectra
+pub fn classify(previous: &[f32], current: &[f32], front_end: &SpectralFrontEnd) -> Hit {
+    let mut added: Vec<f32> = current.iter().zip(previous).map(|(&now, &before)| (now - before).max(0.0)).collect();
+    let mut total: f32 = added.iter().sum();
+    if total <= 0.0 {
+        // Nothing new arrived (onset found on a decaying frame) - fall back to the frame itself
+        added = current.to_vec();
+        total = added.iter().sum::<f32>().max(f32::EPSILON);
+    }
+
+    let band_share = |range: std::ops::Range<f32>| {
+        added.iter()
+            .enumerate()
+            .filter(|&(bin, _)| range.contains(&front_end.bin_frequency(bin)))
+            .map(|(_, &p)| p)
+            .sum::<f32>() / total
+    };
+
+    if band_share(0.0..KICK_MAX_HZ) > 0.5 {
+        Hit::Kick
+    } else if spectral_flatness(current, front_end) < 0.03 {
+        Hit::Tonal
+    } else if band_share(HAT_MIN_HZ..f32::INFINITY) > 0.4 {
+        Hit::Hat
+    } else {
+        Hit::Snare
+    }
+}
+
+/// Geometric over arithmetic mean power between 100Hz and 10kHz: ~0 for tones, higher for noise
+fn spectral_flatness(power: &[f32], front_end: &SpectralFrontEnd) -> f32 {
+    let band: Vec<f32> = power.iter()
+        .enumerate()
+        .filter(|&(bin, _)| (100.0..10000.0).contains(&front_end.bin_frequency(bin)))
+        .map(|(_, &p)| p.max(1e-12))
+        .collect();
+    if band.is_empty() {
+        return 1.0;
+    }
+
+    let log_mean = band.iter().map(|p| p.ln()).sum::<f32>() / band.len() as f32;
+    let mean = band.iter().sum::<f32>() / band.len() as f32;
+    log_mean.exp() / mean
+}
+
+/// Tempo from the autocorrelation of the flux. Each candidate period also scores
+/// the correlation at twice its lag, and candidates are weighted towards 120 BPM,
+/// so a pattern isn't reported at double or half its tempo.
+pub fn estimate_tempo(flux: &[f32], frame_rate: f32) -> Option<f32> {
+    let min_lag = (frame_rate * 60.0 / MAX_BPM).floor().max(1.0) as usize;
+    let max_lag = (frame_rate * 60.0 / MIN_BPM).ceil() as usize;
+    // Twice the slowest period, plus some overlap left at that lag
+    if flux.len() < 3 * max_lag {
+        return None;
+    }
+
+    let mean = flux.iter().sum::<f32>() / flux.len() as f32;
+    let centered: Vec<f32> = flux.iter().map(|f| f - mean).collect();
+    let energy: f32 = centered.iter().map(|f| f * f).sum();
+    if energy <= f32::EPSILON {
+        return None;
+    }
+
+    let correlation = |lag: usize| -> f32 {
+        let sum: f32 = centered.iter().zip(&centered[lag..]).map(|(a, b)| a * b).sum();
+        sum / (centered.len() - lag) as f32
+    };
+    let scores: Vec<f32> = (min_lag..=max_lag)
+        .map(|lag| {
+            let bpm = frame_rate * 60.0 / lag as f32;
+            let octaves = (bpm / PREFERRED_BPM).log2();
+            (correlation(lag) + correlation(2 * lag)) * (-0.5 * octaves * octaves).exp()
+        })
+        .collect();
+
+    let best = (0..scores.len()).max_by(|&a, &b| scores[a].total_cmp(&scores[b]))?;
+    if scores[best] <= 0.0 {
+        return None;
+    }
+
+    // Parabolic interpolation for a fractional lag
+    let lag = if best > 0 && best + 1 < scores.len() {
+        let (a, b, c) = (scores[best - 1], scores[best], scores[best + 1]);
+        let denom = a - 2.0 * b + c;
+        let offset = if denom.abs() > f32::EPSILON { 0.5 * (a - c) / denom } else { 0.0 };
+        (min_lag + best) as f32 + offset.clamp(-0.5, 0.5)
+    } else {
+        (min_lag + best) as f32
+    };
+
+    Some(frame_rate * 60.0 / lag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use std::f32::consts::PI;
+    use crate::rng::MindRng;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    /// Decaying noise clicks at a steady tempo, starting half a beat in
+    fn click_track(bpm: f32, seconds: f32) -> (Vec<f32>, Vec<f32>) {
+        let mut rng = MindRng::from_seed(1);
+        let mut samples = vec![0.0; (SAMPLE_RATE as f32 * seconds) as usize];
+        let beat = 60.0 / bpm;
+        let clicks: Vec<f32> = (0..).map(|i| (i as f32 + 0.5) * beat).take_while(|&t| t + 0.1 < seconds).collect();
+        for &click in &clicks {
+            let start = (click * SAMPLE_RATE as f32) as usize;
+            for (i, sample) in samples[start..start + 441].iter_mut().enumerate() {
+                *sample = rng.gen_range(-1.0..1.0) * (-(i as f32) / 80.0).exp();
+            }
+        }
+        (samples, clicks)
+    }
+
+    fn burst(sample: impl FnMut(usize) -> f32) -> Vec<f32> {
+        (0..2048).map(sample).collect()
+    }
+
+    #[test]
+    fn click_track_onsets_and_tempo() {
+        let front_end = SpectralFrontEnd::default_for(SAMPLE_RATE);
+        // An onset's frame is the first window that reaches the click
+        let window = front_end.fft_size as f32 / SAMPLE_RATE as f32;
+
+        for bpm in [120.0, 100.0] {
+            let (samples, clicks) = click_track(bpm, 8.0);
+            let analysis = analyze(&samples, &front_end);
+
+            assert_eq!(analysis.onsets.len(), clicks.len(), "{} BPM", bpm);
+            for (onset, click) in analysis.onsets.iter().zip(&clicks) {
+                assert!(onset.time <= *click && onset.time > click - window, "onset at {} for a click at {}", onset.time, click);
+            }
+
+            let tempo = analysis.tempo.expect("a steady click track has a tempo");
+            assert!((tempo - bpm).abs() < 2.0, "{} BPM read as {}", bpm, tempo);
+        }
+    }
+
+    #[test]
+    fn silence_and_short_input_have_no_onsets_or_tempo() {
+        let front_end = SpectralFrontEnd::default_for(SAMPLE_RATE);
+        let silence = analyze(&vec![0.0; SAMPLE_RATE as usize * 4], &front_end);
+        assert!(silence.onsets.is_empty());
+        assert!(silence.tempo.is_none());
+
+        assert!(pick_onsets(&[]).is_empty());
+        assert!(estimate_tempo(&[0.0, 5.0, 0.0], 86.0).is_none());
+    }
+
+    #[test]
+    fn flux_only_counts_increases() {
+        let quiet = vec![0.0; 4];
+        let loud = vec![1.0; 4];
+        let flux = spectral_flux(&[quiet.clone(), loud, quiet]);
+        assert_eq!(flux[0], 0.0);
+        assert!(flux[1] > 0.0);
+        assert_eq!(flux[2], 0.0);
+    }
+
+    #[test]
+    fn bursts_are_classified_by_where_their_energy_is() {
+        let front_end = SpectralFrontEnd::default_for(SAMPLE_RATE);
+        let silence = front_end.power_spectrum(&[]);
+        let hit = |samples: Vec<f32>| classify(&silence, &front_end.power_spectrum(&samples), &front_end);
+
+        let rate = SAMPLE_RATE as f32;
+        let mut rng = MindRng::from_seed(2);
+        assert_eq!(hit(burst(|i| (2.0 * PI * 60.0 * i as f32 / rate).sin())), Hit::Kick);
+        assert_eq!(hit(burst(|_| rng.gen_range(-1.0..1.0))), Hit::Hat);
+        assert_eq!(hit(burst(|i| (2.0 * PI * 1000.0 * i as f32 / rate).sin())), Hit::Tonal);
+    }
+}
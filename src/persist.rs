@@ -0,0 +1,454 @@
+// Versioned on-disk format for mind_state.bin
+//
+// Layout: MAGIC (4 bytes) | format version (u32 LE) | bincode payload
+//
+// The payload for FORMAT_VERSION is FractalMind itself. When the persisted fields of
+// FractalMind (or the memory types it contains) change:
+//   1. freeze the current layout here as a MindStateV<n> struct with its own Decode derive
+//   2. bump FORMAT_VERSION
+//   3. add a decode_payload arm that decodes MindStateV<n> and migrates it forward
+// Files written before the header existed are read as version 0.
+//
+// Saves are atomic (temp file + fsync + rename) and the previous state is kept as rotating,
+// timestamped snapshots in <stem>_snapshots/ next to the state file.
+
+use std::fmt;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use bincode::{Encode, Decode};
+use bincode::error::EncodeError;
+
+use crate::config::MindConfig;
+use crate::fractal::MandelbrotCoord;
+use crate::hamming::HammingIndex;
+use crate::hashing::CoordHasherKind;
+use crate::text_coords::TextCoordMapper;
+use crate::memory::{FractalSymbol, ConceptTrajectory, AssociativeField};
+use crate::mind::FractalMind;
+use crate::rng::MindRng;
+use crate::spatial::SpatialIndex;
+
+pub const MAGIC: [u8; 4] = *b"FMND";
+pub const FORMAT_VERSION: u32 = 2;
+
+const HEADER_LEN: usize = MAGIC.len() + 4;
+
+pub const DEFAULT_SNAPSHOT_COUNT: usize = 5;
+// Saves happen every few seconds - don't let them rotate all history out of the window
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Why a mind state couldn't be read or written
+#[derive(Debug)]
+pub enum MindStateError {
+    Io(std::io::Error),
+    /// Written by a newer (or unknown) format version
+    UnsupportedVersion { found: u32, supported: u32 },
+    /// Header or payload doesn't decode
+    Corrupt(String),
+    Encode(EncodeError),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for MindStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MindStateError::Io(e) => write!(f, "mind state i/o error: {}", e),
+            MindStateError::UnsupportedVersion { found, supported } => write!(
+                f,
+                "mind state format version {} is not supported (this build reads up to version {})",
+                found, supported
+            ),
+            MindStateError::Corrupt(reason) => write!(f, "mind state is corrupt: {}", reason),
+            MindStateError::Encode(e) => write!(f, "failed to encode mind state: {}", e),
+            MindStateError::Json(e) => write!(f, "mind state json error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MindStateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MindStateError::Io(e) => Some(e),
+            MindStateError::Encode(e) => Some(e),
+            MindStateError::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for MindStateError {
+    fn from(e: std::io::Error) -> Self {
+        MindStateError::Io(e)
+    }
+}
+
+impl From<EncodeError> for MindStateError {
+    fn from(e: EncodeError) -> Self {
+        MindStateError::Encode(e)
+    }
+}
+
+impl From<serde_json::Error> for MindStateError {
+    fn from(e: serde_json::Error) -> Self {
+        MindStateError::Json(e)
+    }
+}
+
+/// Serde adapter for learned floats in the JSON export. JSON numbers can't hold NaN or
+/// infinity (serde_json writes them as null, which doesn't read back), so those are written
+/// as the strings "NaN", "inf" and "-inf" instead.
+pub mod json_float {
+    use std::fmt;
+    use serde::{Deserializer, Serializer};
+    use serde::de::{self, Visitor};
+
+    pub trait JsonFloat: Copy {
+        fn to_f64(self) -> f64;
+        fn from_f64(value: f64) -> Self;
+        fn serialize_finite<S: Serializer>(self, serializer: S) -> Result<S::Ok, S::Error>;
+    }
+
+    impl JsonFloat for f32 {
+        fn to_f64(self) -> f64 { self as f64 }
+        fn from_f64(value: f64) -> Self { value as f32 }
+        fn serialize_finite<S: Serializer>(self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_f32(self)
+        }
+    }
+
+    impl JsonFloat for f64 {
+        fn to_f64(self) -> f64 { self }
+        fn from_f64(value: f64) -> Self { value }
+        fn serialize_finite<S: Serializer>(self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_f64(self)
+        }
+    }
+
+    pub fn serialize<T: JsonFloat, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        let v = value.to_f64();
+        if v.is_nan() {
+            serializer.serialize_str("NaN")
+        } else if v.is_infinite() {
+            serializer.serialize_str(if v > 0.0 { "inf" } else { "-inf" })
+        } else {
+            value.serialize_finite(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, T: JsonFloat, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_any(FloatVisitor).map(T::from_f64)
+    }
+
+    struct FloatVisitor;
+
+    impl Visitor<'_> for FloatVisitor {
+        type Value = f64;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a number, \"NaN\", \"inf\" or \"-inf\"")
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<f64, E> {
+            Ok(v)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<f64, E> {
+            Ok(v as f64)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<f64, E> {
+            match v {
+                "NaN" => Ok(f64::NAN),
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                other => Err(E::invalid_value(de::Unexpected::Str(other), &self)),
+            }
+        }
+    }
+}
+
+/// Header + current-version payload
+pub fn encode_mind(mind: &FractalMind) -> Result<Vec<u8>, MindStateError> {
+    let cfg = bincode::config::standard();
+    let mut bytes = Vec::with_capacity(HEADER_LEN);
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.extend(bincode::encode_to_vec(mind, cfg)?);
+    Ok(bytes)
+}
+
+/// Read any supported version, migrating older layouts to the current one
+pub fn decode_mind(data: &[u8]) -> Result<FractalMind, MindStateError> {
+    if data.len() < HEADER_LEN || data[..MAGIC.len()] != MAGIC {
+        // Pre-header file: the raw bincode FractalMind
+        return decode_payload(0, data);
+    }
+
+    let mut version_bytes = [0u8; 4];
+    version_bytes.copy_from_slice(&data[MAGIC.len()..HEADER_LEN]);
+    let version = u32::from_le_bytes(version_bytes);
+
+    decode_payload(version, &data[HEADER_LEN..])
+}
+
+fn decode_payload(version: u32, payload: &[u8]) -> Result<FractalMind, MindStateError> {
+    match version {
+        // Version 0 (headerless) has the same layout as version 1
+        0 | 1 => decode_exact::<MindStateV1>(payload).map(FractalMind::from),
+        2 => decode_exact(payload),
+        found => Err(MindStateError::UnsupportedVersion { found, supported: FORMAT_VERSION }),
+    }
+}
+
+/// Layout of versions 0 and 1 - before the config, RNG, hashers and field members were saved
+#[derive(Encode, Decode)]
+struct MindStateV1 {
+    current_coord: MandelbrotCoord,
+    symbols: Vec<FractalSymbol>,
+    trajectories: Vec<ConceptTrajectory>,
+    associative_fields: Vec<AssociativeFieldV1>,
+    context_history: Vec<String>,
+    symbol_history: Vec<usize>,
+    background_thought_coord: Option<MandelbrotCoord>,
+    contextual_coord: MandelbrotCoord,
+    last_output: String,
+    inhibited_symbols: Vec<(usize, u32)>,
+    inhibited_trajectories: Vec<(usize, u32)>,
+    scale: u32,
+    exploration_radius: f64,
+}
+
+// v1 minds ran on what are now the MindConfig defaults, drew from thread_rng (there is no
+// stream to continue, so start a fresh one) and hashed whole strings with std's DefaultHasher,
+// which SipHash13 reproduces exactly
+impl From<MindStateV1> for FractalMind {
+    fn from(v1: MindStateV1) -> Self {
+        FractalMind {
+            current_coord: v1.current_coord,
+            symbols: v1.symbols,
+            trajectories: v1.trajectories,
+            associative_fields: v1.associative_fields.into_iter().map(AssociativeField::from).collect(),
+            context_history: v1.context_history,
+            symbol_history: v1.symbol_history,
+            background_thought_coord: v1.background_thought_coord,
+            contextual_coord: v1.contextual_coord,
+            last_output: v1.last_output,
+            inhibited_symbols: v1.inhibited_symbols,
+            inhibited_trajectories: v1.inhibited_trajectories,
+            scale: v1.scale,
+            exploration_radius: v1.exploration_radius,
+            config: MindConfig::default(),
+            coord_hasher: CoordHasherKind::SipHash13,
+            text_mapper: TextCoordMapper::Hashed,
+            rng: MindRng::from_entropy(),
+            spatial_index: SpatialIndex::new(),
+            fingerprint_index: HammingIndex::default(),
+        }
+    }
+}
+
+/// Associative field layout of version 1 - before fields could link trajectories
+#[derive(Encode, Decode)]
+struct AssociativeFieldV1 {
+    center: MandelbrotCoord,
+    radius: f64,
+    strength: f64,
+}
+
+impl From<AssociativeFieldV1> for AssociativeField {
+    fn from(v1: AssociativeFieldV1) -> Self {
+        AssociativeField {
+            strength: v1.strength,
+            ..AssociativeField::new(v1.center, v1.radius)
+        }
+    }
+}
+
+fn decode_exact<T: bincode::Decode<()>>(payload: &[u8]) -> Result<T, MindStateError> {
+    let cfg = bincode::config::standard();
+    let (value, len) = bincode::decode_from_slice(payload, cfg)
+        .map_err(|e| MindStateError::Corrupt(e.to_string()))?;
+    if len != payload.len() {
+        return Err(MindStateError::Corrupt(format!(
+            "{} trailing bytes after payload", payload.len() - len
+        )));
+    }
+    Ok(value)
+}
+
+/// Write `bytes` to `path` so a crash leaves either the old or the new file, never a torn one
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let tmp_path = sibling_path(path, ".tmp");
+
+    {
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+    }
+
+    fs::rename(&tmp_path, path)?;
+
+    // Make the rename itself durable
+    #[cfg(unix)]
+    {
+        let parent = path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        File::open(parent)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+/// Snapshot the current state file if the newest snapshot is older than SNAPSHOT_INTERVAL
+pub fn rotate_snapshots(path: &Path, keep: usize) -> std::io::Result<()> {
+    let due = match list_snapshots(path)?.first() {
+        Some((_, taken)) => SystemTime::now().duration_since(*taken).unwrap_or_default() >= SNAPSHOT_INTERVAL,
+        None => true,
+    };
+
+    if due {
+        snapshot(path, keep)?;
+    }
+    Ok(())
+}
+
+/// Preserve the current state file as a timestamped snapshot, pruning all but the newest `keep`
+pub fn snapshot(path: &Path, keep: usize) -> std::io::Result<()> {
+    if keep == 0 || !path.exists() {
+        return Ok(());
+    }
+
+    let dir = snapshot_dir(path);
+    fs::create_dir_all(&dir)?;
+
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let target = dir.join(format!("{}.{}.bin", file_stem(path), secs));
+
+    if !target.exists() {
+        // The state file is only ever replaced by rename, so a hard link is a stable copy
+        if fs::hard_link(path, &target).is_err() {
+            fs::copy(path, &target)?;
+        }
+    }
+
+    for (old, _) in list_snapshots(path)?.into_iter().skip(keep) {
+        fs::remove_file(old)?;
+    }
+    Ok(())
+}
+
+/// Snapshots of `path`, newest first
+pub fn list_snapshots(path: &Path) -> std::io::Result<Vec<(PathBuf, SystemTime)>> {
+    let dir = snapshot_dir(path);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let prefix = format!("{}.", file_stem(path));
+    let mut snapshots = Vec::new();
+
+    for entry in fs::read_dir(&dir)? {
+        let entry_path = entry?.path();
+        let Some(name) = entry_path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let secs = name.strip_prefix(&prefix)
+            .and_then(|rest| rest.strip_suffix(".bin"))
+            .and_then(|ts| ts.parse::<u64>().ok());
+
+        if let Some(secs) = secs {
+            snapshots.push((entry_path, UNIX_EPOCH + Duration::from_secs(secs)));
+        }
+    }
+
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.1));
+    Ok(snapshots)
+}
+
+fn snapshot_dir(path: &Path) -> PathBuf {
+    path.with_file_name(format!("{}_snapshots", file_stem(path)))
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().and_then(|s| s.to_str()).unwrap_or("mind_state").to_string()
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v1_mind() -> MindStateV1 {
+        let coord = MandelbrotCoord::new(-0.75, 0.1);
+        MindStateV1 {
+            current_coord: coord,
+            symbols: vec![FractalSymbol::new(coord, vec![0xf0f0; 32], 0.5)],
+            trajectories: vec![ConceptTrajectory::new(vec![coord, MandelbrotCoord::new(-0.7, 0.2)], String::new(), vec![0])],
+            associative_fields: vec![AssociativeFieldV1 { center: coord, radius: 0.2, strength: 2.5 }],
+            context_history: vec!["hello".to_string()],
+            symbol_history: vec![0],
+            background_thought_coord: None,
+            contextual_coord: coord,
+            last_output: "hi".to_string(),
+            inhibited_symbols: vec![(0, 3)],
+            inhibited_trajectories: Vec::new(),
+            scale: 64,
+            exploration_radius: 0.3,
+        }
+    }
+
+    #[test]
+    fn migrates_headerless_and_v1_files() {
+        let payload = bincode::encode_to_vec(v1_mind(), bincode::config::standard()).unwrap();
+        let mut headered = MAGIC.to_vec();
+        headered.extend(1u32.to_le_bytes());
+        headered.extend(&payload);
+
+        for data in [payload, headered] {
+            let mind = decode_mind(&data).unwrap();
+            assert_eq!(mind.symbols.len(), 1);
+            assert_eq!(mind.trajectories[0].path.len(), 2);
+            assert_eq!(mind.associative_fields[0].strength, 2.5);
+            assert!(mind.associative_fields[0].members.is_empty());
+            assert_eq!((mind.last_output.as_str(), mind.scale), ("hi", 64));
+            assert_eq!(mind.coord_hasher, CoordHasherKind::SipHash13);
+            assert_eq!(mind.text_mapper, TextCoordMapper::Hashed);
+
+            let cfg = bincode::config::standard();
+            assert_eq!(
+                bincode::encode_to_vec(&mind.config, cfg).unwrap(),
+                bincode::encode_to_vec(MindConfig::default(), cfg).unwrap()
+            );
+
+            // Saved again, it reads back as the current version
+            let resaved = encode_mind(&mind).unwrap();
+            assert_eq!(resaved[MAGIC.len()..HEADER_LEN], FORMAT_VERSION.to_le_bytes());
+            assert_eq!(encode_mind(&decode_mind(&resaved).unwrap()).unwrap(), resaved);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_future_version() {
+        let mut data = MAGIC.to_vec();
+        data.extend((FORMAT_VERSION + 1).to_le_bytes());
+        data.extend([0u8; 16]);
+
+        match decode_mind(&data) {
+            Err(MindStateError::UnsupportedVersion { found, supported }) => {
+                assert_eq!((found, supported), (FORMAT_VERSION + 1, FORMAT_VERSION));
+            }
+            Err(other) => panic!("expected UnsupportedVersion, got {}", other),
+            Ok(_) => panic!("a future version decoded"),
+        }
+    }
+}
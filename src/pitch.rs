@@ -0,0 +1,205 @@
+// Pitch tracking for audio encoding
+// YIN fundamental estimation with a confidence value, musical note naming,
+// and spectral peak picking for chords and other polyphonic input.
+
+use crate::spectral::SpectralFrontEnd;
+
+/// YIN threshold on the cumulative mean normalized difference
+const YIN_THRESHOLD: f32 = 0.15;
+
+const NOTE_NAMES: [&str; 12] = ["C", "C#", "D", "D#", "E", "F", "F#", "G", "G#", "A", "A#", "B"];
+
+#[derive(Clone, Copy, Debug)]
+pub struct PitchEstimate {
+    pub frequency: f32,
+    pub confidence: f32,   // 1 - YIN aperiodicity: ~1 for a clean tone, ~0 for noise
+}
+
+/// YIN (de Cheveigné & Kawahara 2002) over the last part of `samples`,
+/// searching fundamentals between min_hz and max_hz (None for an empty or invalid range)
+pub fn yin(samples: &[f32], sample_rate: u32, min_hz: f32, max_hz: f32) -> Option<PitchEstimate> {
+    if !(min_hz.is_finite() && max_hz.is_finite() && min_hz > 0.0 && max_hz > min_hz) {
+        return None;
+    }
+
+    let sr = sample_rate as f32;
+    let tau_min = ((sr / max_hz).floor() as usize).max(2);
+    let tau_max = (sr / min_hz).ceil() as usize;
+    if tau_min >= tau_max {
+        return None;
+    }
+
+    // Integration window: enough for two periods of the lowest pitch
+    let window = tau_max.saturating_mul(2).max(1024);
+    if samples.len() < window.saturating_add(tau_max) {
+        return None;
+    }
+    let frame = &samples[samples.len() - window - tau_max..];
+
+    // Difference function d(tau) and its cumulative mean normalization d'(tau)
+    let mut normalized = vec![1.0f32; tau_max + 1];
+    let mut running_sum = 0.0;
+    for tau in 1..=tau_max {
+        let d: f32 = (0..window).map(|i| {
+            let delta = frame[i] - frame[i + tau];
+            delta * delta
+        }).sum();
+        running_sum += d;
+        normalized[tau] = if running_sum > 0.0 { d * tau as f32 / running_sum } else { 1.0 };
+    }
+
+    // First dip under the threshold (then down to its local minimum), else the global minimum
+    let mut best = None;
+    let mut tau = tau_min;
+    while tau <= tau_max {
+        if normalized[tau] < YIN_THRESHOLD {
+            while tau < tau_max && normalized[tau + 1] < normalized[tau] {
+                tau += 1;
+            }
+            best = Some(tau);
+            break;
+        }
+        tau += 1;
+    }
+    let tau = best.unwrap_or_else(|| {
+        (tau_min..=tau_max)
+            .min_by(|&a, &b| normalized[a].total_cmp(&normalized[b]))
+            .unwrap_or(tau_min)
+    });
+
+    // Parabolic interpolation for sub-sample period
+    let refined = if tau > tau_min && tau < tau_max {
+        let (a, b, c) = (normalized[tau - 1], normalized[tau], normalized[tau + 1]);
+        let denom = a - 2.0 * b + c;
+        if denom.abs() > f32::EPSILON { tau as f32 + 0.5 * (a - c) / denom } else { tau as f32 }
+    } else {
+        tau as f32
+    };
+
+    Some(PitchEstimate {
+        frequency: sr / refined,
+        confidence: (1.0 - normalized[tau]).clamp(0.0, 1.0),
+    })
+}
+
+/// Nearest equal-tempered note (A4 = 440Hz)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Note {
+    pub name: &'static str,
+    pub octave: i32,
+    pub cents: i32,   // Deviation from the note, -50..=50
+}
+
+impl Note {
+    pub fn from_frequency(frequency: f32) -> Option<Note> {
+        if frequency <= 0.0 || !frequency.is_finite() {
+            return None;
+        }
+
+        let midi = 69.0 + 12.0 * (frequency / 440.0).log2();
+        let nearest = midi.round();
+        let semitone = nearest as i32;
+
+        Some(Note {
+            name: NOTE_NAMES[semitone.rem_euclid(12) as usize],
+            octave: semitone.div_euclid(12) - 1,
+            cents: ((midi - nearest) * 100.0).round() as i32,
+        })
+    }
+}
+
+impl std::fmt::Display for Note {
+    /// "A4", or "A4+12c" / "A4-7c" when off pitch
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.name, self.octave)?;
+        if self.cents != 0 {
+            write!(f, "{:+}c", self.cents)?;
+        }
+        Ok(())
+    }
+}
+
+/// The strongest spectral peaks between min_hz and max_hz, strongest first.
+/// Peaks more than 30dB below the strongest one, or less than 10dB above the
+/// median (noise floor) of the range, are ignored.
+pub fn spectral_peaks(power: &[f32], front_end: &SpectralFrontEnd, min_hz: f32, max_hz: f32, count: usize) -> Vec<f32> {
+    let strongest = power.iter().cloned().fold(0.0, f32::max);
+    let mut in_range: Vec<f32> = (0..power.len())
+        .filter(|&bin| (min_hz..=max_hz).contains(&front_end.bin_frequency(bin)))
+        .map(|bin| power[bin])
+        .collect();
+    in_range.sort_by(f32::total_cmp);
+    let median = in_range.get(in_range.len() / 2).copied().unwrap_or(0.0);
+    let floor = (strongest * 1e-3).max(median * 10.0).max(1e-9);
+
+    let mut peaks: Vec<(f32, f32)> = (1..power.len().saturating_sub(1))
+        .filter(|&bin| power[bin] > floor && power[bin] > power[bin - 1] && power[bin] >= power[bin + 1])
+        .filter_map(|bin| {
+            // Parabolic interpolation on log power
+            let (a, b, c) = (power[bin - 1].max(1e-12).ln(), power[bin].ln(), power[bin + 1].max(1e-12).ln());
+            let denom = a - 2.0 * b + c;
+            let offset = if denom.abs() > f32::EPSILON { 0.5 * (a - c) / denom } else { 0.0 };
+            let frequency = front_end.bin_frequency(bin) + offset * front_end.bin_frequency(1);
+            (frequency >= min_hz && frequency <= max_hz).then_some((frequency, power[bin]))
+        })
+        .collect();
+
+    peaks.sort_by(|a, b| b.1.total_cmp(&a.1));
+    peaks.into_iter().take(count).map(|(frequency, _)| frequency).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    fn tones(frequencies: &[f32], seconds: f32) -> Vec<f32> {
+        (0..(SAMPLE_RATE as f32 * seconds) as usize)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                frequencies.iter().map(|f| (2.0 * PI * f * t).sin()).sum::<f32>() / frequencies.len() as f32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn yin_finds_a_sine() {
+        for frequency in [110.0, 440.0, 987.77] {
+            let estimate = yin(&tones(&[frequency], 0.2), SAMPLE_RATE, 50.0, 2000.0).unwrap();
+            assert!((estimate.frequency - frequency).abs() < frequency * 0.005, "{} Hz read as {}", frequency, estimate.frequency);
+            assert!(estimate.confidence > 0.9);
+        }
+
+        let note = Note::from_frequency(440.0).unwrap();
+        assert_eq!((note.name, note.octave, note.cents), ("A", 4, 0));
+        assert_eq!(Note::from_frequency(987.77).unwrap().to_string(), "B5");
+    }
+
+    #[test]
+    fn spectral_peaks_find_every_note_of_a_chord() {
+        // C major: C4, E4, G4
+        let chord = [261.63, 329.63, 392.0];
+        let front_end = SpectralFrontEnd::new(SAMPLE_RATE, 8192, 2048, crate::spectral::WindowFunction::Hann);
+        let power = front_end.average_power_spectrum(&tones(&chord, 0.5));
+
+        let mut peaks = spectral_peaks(&power, &front_end, 50.0, 2000.0, 3);
+        peaks.sort_by(f32::total_cmp);
+        let names: Vec<String> = peaks.into_iter()
+            .filter_map(Note::from_frequency)
+            .map(|note| format!("{}{}", note.name, note.octave))
+            .collect();
+        assert_eq!(names, ["C4", "E4", "G4"]);
+    }
+
+    #[test]
+    fn yin_rejects_invalid_ranges() {
+        let samples = tones(&[440.0], 0.2);
+        for (min_hz, max_hz) in [(0.0, 1000.0), (-50.0, 1000.0), (f32::NAN, 1000.0), (50.0, f32::INFINITY), (500.0, 400.0), (440.0, 440.0)] {
+            assert!(yin(&samples, SAMPLE_RATE, min_hz, max_hz).is_none(), "{}..{}", min_hz, max_hz);
+        }
+        // Positive but tiny - a huge search range that no buffer can fill
+        assert!(yin(&samples, SAMPLE_RATE, 1e-30, 1000.0).is_none());
+    }
+}
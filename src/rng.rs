@@ -0,0 +1,96 @@
+// Seedable, persistable randomness for FractalMind
+// Same seed + same inputs + same saved state = same outputs
+
+use bincode::{Encode, Decode};
+use bincode::enc::Encoder;
+use bincode::de::Decoder;
+use bincode::error::{EncodeError, DecodeError};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+/// ChaCha12 stream seeded from a u64. Its whole state is (seed, word position),
+/// which is what gets saved - a loaded mind continues the exact same stream.
+#[derive(Clone, Debug)]
+pub struct MindRng {
+    seed: u64,
+    rng: ChaCha12Rng,
+}
+
+impl MindRng {
+    pub fn from_seed(seed: u64) -> Self {
+        MindRng {
+            seed,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Fresh seed from the OS - used when no --seed is given
+    pub fn from_entropy() -> Self {
+        Self::from_seed(rand::random())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn from_state(seed: u64, word_pos: u128) -> Self {
+        let mut rng = Self::from_seed(seed);
+        rng.rng.set_word_pos(word_pos);
+        rng
+    }
+}
+
+impl RngCore for MindRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl Encode for MindRng {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.seed.encode(encoder)?;
+        self.rng.get_word_pos().encode(encoder)
+    }
+}
+
+impl<Context> Decode<Context> for MindRng {
+    fn decode<D: Decoder<Context = Context>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        let seed = u64::decode(decoder)?;
+        let word_pos = u128::decode(decoder)?;
+        Ok(MindRng::from_state(seed, word_pos))
+    }
+}
+
+bincode::impl_borrow_decode!(MindRng);
+
+#[derive(Serialize, Deserialize)]
+struct MindRngState {
+    seed: u64,
+    word_pos: u128,
+}
+
+impl Serialize for MindRng {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        MindRngState { seed: self.seed, word_pos: self.rng.get_word_pos() }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MindRng {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = MindRngState::deserialize(deserializer)?;
+        Ok(MindRng::from_state(state.seed, state.word_pos))
+    }
+}
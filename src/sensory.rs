@@ -0,0 +1,107 @@
+// Numeric sensory input for FractalMind
+// Feature vectors go straight to the Mandelbrot plane through a fixed random projection,
+// so similar stimuli land near each other instead of being hashed apart as strings.
+
+use rand::Rng;
+
+use crate::fractal::{MandelbrotCoord, plane_to_mandelbrot_coord};
+use crate::hashing::{CoordHasher, Fnv1a};
+use crate::rng::MindRng;
+
+/// Base seed of every projection - changing it relocates all sensory memories
+const PROJECTION_SEED: u64 = 0x0073_656e_736f_7279;
+/// Projected distance that spans about half of the region on each axis
+const SPREAD: f64 = 0.5;
+
+/// A feature vector from one sensor, values roughly in 0..1
+#[derive(Clone, Debug)]
+pub struct SensoryVector {
+    pub modality: &'static str,   // "pixels", "fft", "blocks", ... - each gets its own projection
+    pub values: Vec<f32>,
+}
+
+impl SensoryVector {
+    pub fn new(modality: &'static str, values: Vec<f32>) -> Self {
+        SensoryVector { modality, values }
+    }
+}
+
+/// Gaussian random projection from one modality's feature space to the plane.
+/// Distances between projected points are, in expectation, the RMS per-feature
+/// difference of the vectors, whatever their length.
+pub struct SensoryProjection {
+    axes: [Vec<f64>; 2],   // One Gaussian direction per output axis (re, im)
+}
+
+impl SensoryProjection {
+    /// The projection for a modality and vector length - always the same one, so
+    /// coordinates stay put across sessions without saving the matrix
+    pub fn new(modality: &str, len: usize) -> Self {
+        let seed = PROJECTION_SEED ^ Fnv1a.hash_str(modality) ^ (len as u64).rotate_left(32);
+        let mut rng = MindRng::from_seed(seed);
+        let scale = 1.0 / (len.max(1) as f64).sqrt();
+        let mut axis = || (0..len).map(|_| gaussian(&mut rng) * scale).collect();
+        SensoryProjection { axes: [axis(), axis()] }
+    }
+
+    /// Project around the mid value 0.5, then squash smoothly into the region.
+    /// Values past the projection's length are ignored.
+    pub fn project(&self, values: &[f32]) -> MandelbrotCoord {
+        let [re, im] = self.axes.each_ref().map(|axis| {
+            axis.iter().zip(values).map(|(w, &v)| w * (v as f64 - 0.5)).sum::<f64>()
+        });
+
+        plane_to_mandelbrot_coord(re / SPREAD, im / SPREAD)
+    }
+}
+
+/// Where a sensory vector lands in the plane
+pub fn sensory_to_mandelbrot_coord(vector: &SensoryVector) -> MandelbrotCoord {
+    SensoryProjection::new(vector.modality, vector.values.len()).project(&vector.values)
+}
+
+/// Standard normal sample (Box-Muller)
+fn gaussian(rng: &mut MindRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.r#gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::ConceptTrajectory;
+
+    fn random_values(rng: &mut MindRng, len: usize) -> Vec<f32> {
+        (0..len).map(|_| rng.gen_range(0.0..1.0)).collect()
+    }
+
+    #[test]
+    fn near_vectors_land_nearer_than_unrelated_ones() {
+        let mut rng = MindRng::from_seed(42);
+        for _ in 0..20 {
+            let base = random_values(&mut rng, 64);
+            let perturbed: Vec<f32> = base.iter().map(|v| v + rng.gen_range(-0.02..0.02)).collect();
+            let unrelated = random_values(&mut rng, 64);
+
+            let at = |values: &[f32]| sensory_to_mandelbrot_coord(&SensoryVector::new("pixels", values.to_vec()));
+            let near = ConceptTrajectory::coord_distance(at(&base), at(&perturbed));
+            let far = ConceptTrajectory::coord_distance(at(&base), at(&unrelated));
+            assert!(near < far, "perturbed copy at {near}, unrelated vector at {far}");
+        }
+    }
+
+    #[test]
+    fn projections_are_stable_across_calls() {
+        let mut rng = MindRng::from_seed(7);
+        let vector = SensoryVector::new("fft", random_values(&mut rng, 32));
+
+        let first = sensory_to_mandelbrot_coord(&vector);
+        let again = SensoryProjection::new("fft", 32).project(&vector.values);
+        assert_eq!((first.re, first.im), (again.re, again.im));
+
+        // Another modality gets its own projection
+        let other = sensory_to_mandelbrot_coord(&SensoryVector::new("blocks", vector.values.clone()));
+        assert_ne!((first.re, first.im), (other.re, other.im));
+    }
+}
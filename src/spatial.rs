@@ -0,0 +1,245 @@
+// Spatial index over the Mandelbrot region used for concept coordinates
+// Uniform grid so trajectory/symbol lookups only touch nearby cells instead of scanning everything
+
+use bincode::{Encode, Decode};
+use bincode::enc::Encoder;
+use bincode::de::Decoder;
+use bincode::error::{EncodeError, DecodeError};
+
+use crate::fractal::MandelbrotCoord;
+use crate::memory::{FractalSymbol, ConceptTrajectory};
+
+// Same region hash_to_mandelbrot_coord maps into
+const RE_MIN: f64 = -0.75;
+const RE_MAX: f64 = 0.25;
+const IM_MIN: f64 = -1.0;
+const IM_MAX: f64 = 1.0;
+const CELL_SIZE: f64 = 0.025;  // 40 x 80 cells
+
+/// Uniform grid of trajectory points and symbol coordinates.
+/// Derived data - never persisted, rebuilt from the mind after loading.
+pub struct SpatialIndex {
+    cols: usize,
+    rows: usize,
+    trajectory_cells: Vec<Vec<(usize, usize, MandelbrotCoord)>>,  // (traj_idx, point_idx, point) - the point kept inline to skip a lookup
+    symbol_cells: Vec<Vec<usize>>,  // symbol_idx
+}
+
+impl SpatialIndex {
+    pub fn new() -> Self {
+        let cols = ((RE_MAX - RE_MIN) / CELL_SIZE).ceil() as usize;
+        let rows = ((IM_MAX - IM_MIN) / CELL_SIZE).ceil() as usize;
+        SpatialIndex {
+            cols,
+            rows,
+            trajectory_cells: vec![Vec::new(); cols * rows],
+            symbol_cells: vec![Vec::new(); cols * rows],
+        }
+    }
+
+    /// Rebuild everything from scratch (after load, or when trajectories are pruned)
+    pub fn rebuild(&mut self, trajectories: &[ConceptTrajectory], symbols: &[FractalSymbol]) {
+        for cell in &mut self.trajectory_cells {
+            cell.clear();
+        }
+        for (traj_idx, trajectory) in trajectories.iter().enumerate() {
+            self.insert_trajectory(traj_idx, trajectory);
+        }
+        self.rebuild_symbols(symbols);
+    }
+
+    /// Symbol indices shift when decay_symbols prunes, so re-bucket them all
+    pub fn rebuild_symbols(&mut self, symbols: &[FractalSymbol]) {
+        for cell in &mut self.symbol_cells {
+            cell.clear();
+        }
+        for (symbol_idx, symbol) in symbols.iter().enumerate() {
+            self.insert_symbol(symbol_idx, symbol.coord);
+        }
+    }
+
+    pub fn insert_trajectory(&mut self, traj_idx: usize, trajectory: &ConceptTrajectory) {
+        for (point_idx, &point) in trajectory.path.iter().enumerate() {
+            let cell = self.cell_of(point);
+            self.trajectory_cells[cell].push((traj_idx, point_idx, point));
+        }
+    }
+
+    pub fn insert_symbol(&mut self, symbol_idx: usize, coord: MandelbrotCoord) {
+        let cell = self.cell_of(coord);
+        self.symbol_cells[cell].push(symbol_idx);
+    }
+
+    /// Trajectories with at least one path point within `radius` of `coord`.
+    /// Returns (traj_idx, closest_point_idx, dist) sorted by traj_idx - the closest point
+    /// is the same one ConceptTrajectory::closest_point would pick.
+    pub fn nearby_trajectories(
+        &self,
+        trajectories: &[ConceptTrajectory],
+        coord: MandelbrotCoord,
+        radius: f64,
+    ) -> Vec<(usize, usize, f64)> {
+        // Closest point per trajectory, point_idx usize::MAX while none is in range
+        let mut closest = vec![(usize::MAX, f64::INFINITY); trajectories.len()];
+
+        for cell in self.cells_within(coord, radius) {
+            for &(traj_idx, point_idx, point) in &self.trajectory_cells[cell] {
+                let dist = ConceptTrajectory::coord_distance(coord, point);
+                let best = &mut closest[traj_idx];
+                // Ties go to the earliest point, matching closest_point
+                if dist <= radius && (dist < best.1 || (dist == best.1 && point_idx < best.0)) {
+                    *best = (point_idx, dist);
+                }
+            }
+        }
+
+        closest.into_iter()
+            .enumerate()
+            .filter(|&(_, (point_idx, _))| point_idx != usize::MAX)
+            .map(|(traj_idx, (point_idx, dist))| (traj_idx, point_idx, dist))
+            .collect()
+    }
+
+    /// Trajectories whose first point lies within `radius` of `coord`, sorted by index
+    pub fn trajectories_starting_within(&self, coord: MandelbrotCoord, radius: f64) -> Vec<usize> {
+        let mut result = Vec::new();
+
+        for cell in self.cells_within(coord, radius) {
+            for &(traj_idx, point_idx, point) in &self.trajectory_cells[cell] {
+                if point_idx == 0 && ConceptTrajectory::coord_distance(coord, point) <= radius {
+                    result.push(traj_idx);
+                }
+            }
+        }
+
+        result.sort_unstable();
+        result
+    }
+
+    /// Nearest symbol by coordinate distance (ties go to the lowest index)
+    pub fn nearest_symbol(&self, symbols: &[FractalSymbol], coord: MandelbrotCoord) -> Option<usize> {
+        if symbols.is_empty() {
+            return None;
+        }
+
+        // Grow the search radius until something is found inside it - anything
+        // outside the radius is then guaranteed to be farther away
+        let max_radius = (RE_MAX - RE_MIN).hypot(IM_MAX - IM_MIN)
+            + (coord.re - coord.re.clamp(RE_MIN, RE_MAX)).abs()
+            + (coord.im - coord.im.clamp(IM_MIN, IM_MAX)).abs();
+        let mut radius = CELL_SIZE;
+
+        loop {
+            let mut best: Option<(usize, f64)> = None;
+
+            for cell in self.cells_within(coord, radius) {
+                for &symbol_idx in &self.symbol_cells[cell] {
+                    let dist = ConceptTrajectory::coord_distance(coord, symbols[symbol_idx].coord);
+                    if dist > radius {
+                        continue;
+                    }
+                    let better = match best {
+                        None => true,
+                        Some((best_idx, best_dist)) => dist < best_dist || (dist == best_dist && symbol_idx < best_idx),
+                    };
+                    if better {
+                        best = Some((symbol_idx, dist));
+                    }
+                }
+            }
+
+            if let Some((symbol_idx, _)) = best {
+                return Some(symbol_idx);
+            }
+            if radius > max_radius {
+                return None;
+            }
+            radius *= 2.0;
+        }
+    }
+
+    fn col_of(&self, re: f64) -> usize {
+        let col = ((re - RE_MIN) / CELL_SIZE).floor();
+        col.clamp(0.0, (self.cols - 1) as f64) as usize
+    }
+
+    fn row_of(&self, im: f64) -> usize {
+        let row = ((im - IM_MIN) / CELL_SIZE).floor();
+        row.clamp(0.0, (self.rows - 1) as f64) as usize
+    }
+
+    fn cell_of(&self, coord: MandelbrotCoord) -> usize {
+        self.row_of(coord.im) * self.cols + self.col_of(coord.re)
+    }
+
+    // Coordinates outside the region are clamped into the border cells, so a clamped
+    // bounding box still covers every point that can be within `radius`
+    fn cells_within(&self, coord: MandelbrotCoord, radius: f64) -> impl Iterator<Item = usize> + '_ {
+        let col_lo = self.col_of(coord.re - radius);
+        let col_hi = self.col_of(coord.re + radius);
+        let row_lo = self.row_of(coord.im - radius);
+        let row_hi = self.row_of(coord.im + radius);
+
+        (row_lo..=row_hi).flat_map(move |row| {
+            (col_lo..=col_hi).map(move |col| row * self.cols + col)
+        })
+    }
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// The index is derived state: it encodes to nothing so the saved layout is unchanged,
+// and decodes empty - FractalMind::load rebuilds it
+impl Encode for SpatialIndex {
+    fn encode<E: Encoder>(&self, _encoder: &mut E) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<Context> Decode<Context> for SpatialIndex {
+    fn decode<D: Decoder<Context = Context>>(_decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(SpatialIndex::new())
+    }
+}
+
+bincode::impl_borrow_decode!(SpatialIndex);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fractal::hash_to_mandelbrot_coord;
+
+    #[test]
+    fn index_matches_a_linear_scan() {
+        let trajectories: Vec<ConceptTrajectory> = (0..300)
+            .map(|t| {
+                let path = (0..4).map(|i| hash_to_mandelbrot_coord(&format!("t{}:{}", t, i))).collect();
+                ConceptTrajectory::new(path, String::new(), vec![0; 4])
+            })
+            .collect();
+        let mut index = SpatialIndex::new();
+        index.rebuild(&trajectories, &[]);
+
+        for (q, radius) in [(0, 0.05), (1, 0.2), (2, 0.3), (3, 3.0)] {
+            let coord = hash_to_mandelbrot_coord(&format!("query{}", q));
+
+            let linear: Vec<(usize, usize, f64)> = trajectories.iter().enumerate()
+                .map(|(traj_idx, t)| {
+                    let (point_idx, dist) = t.closest_point(coord);
+                    (traj_idx, point_idx, dist)
+                })
+                .filter(|&(_, _, dist)| dist <= radius)
+                .collect();
+            assert_eq!(index.nearby_trajectories(&trajectories, coord, radius), linear);
+
+            let starting: Vec<usize> = (0..trajectories.len())
+                .filter(|&t| ConceptTrajectory::coord_distance(coord, trajectories[t].path[0]) <= radius)
+                .collect();
+            assert_eq!(index.trajectories_starting_within(coord, radius), starting);
+        }
+    }
+}
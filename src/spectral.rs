@@ -0,0 +1,289 @@
+// Spectral front-end for audio encoding
+// Windowed short-time FFT with a cached plan, mel filterbanks and MFCCs,
+// so audio encodings describe perceptual bands instead of raw linear bins.
+
+use rustfft::{Fft, FftPlanner, num_complex::Complex};
+use std::f32::consts::PI;
+use std::sync::Arc;
+
+/// Analysis window applied to every frame before the FFT
+#[derive(Clone, Copy, Debug)]
+pub enum WindowFunction {
+    Rectangular,
+    Hann,
+    Hamming,
+}
+
+impl WindowFunction {
+    pub fn coefficients(self, size: usize) -> Vec<f32> {
+        let denom = size.saturating_sub(1).max(1) as f32;
+        (0..size)
+            .map(|n| {
+                let phase = 2.0 * PI * n as f32 / denom;
+                match self {
+                    WindowFunction::Rectangular => 1.0,
+                    WindowFunction::Hann => 0.5 - 0.5 * phase.cos(),
+                    WindowFunction::Hamming => 0.54 - 0.46 * phase.cos(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Short-time power spectra with a planned-once FFT.
+/// Power is scaled so a full-scale sine puts ~1.0 (0 dB) in its bin.
+pub struct SpectralFrontEnd {
+    pub sample_rate: u32,
+    pub fft_size: usize,
+    pub hop: usize,
+    fft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    power_scale: f32,
+}
+
+impl SpectralFrontEnd {
+    pub fn new(sample_rate: u32, fft_size: usize, hop: usize, window: WindowFunction) -> Self {
+        let fft_size = fft_size.max(2);
+        let fft = FftPlanner::new().plan_fft_forward(fft_size);
+        let window = window.coefficients(fft_size);
+        let window_sum: f32 = window.iter().sum();
+
+        SpectralFrontEnd {
+            sample_rate,
+            fft_size,
+            hop: hop.max(1),
+            fft,
+            power_scale: 4.0 / (window_sum * window_sum).max(f32::EPSILON),
+            window,
+        }
+    }
+
+    /// 2048-point Hann window, 75% overlap
+    pub fn default_for(sample_rate: u32) -> Self {
+        Self::new(sample_rate, 2048, 512, WindowFunction::Hann)
+    }
+
+    /// Number of bins in a power spectrum (DC through Nyquist)
+    pub fn bin_count(&self) -> usize {
+        self.fft_size / 2 + 1
+    }
+
+    pub fn bin_frequency(&self, bin: usize) -> f32 {
+        bin as f32 * self.sample_rate as f32 / self.fft_size as f32
+    }
+
+    /// Power spectrum of one frame (zero-padded or truncated to fft_size)
+    pub fn power_spectrum(&self, frame: &[f32]) -> Vec<f32> {
+        let mut buffer: Vec<Complex<f32>> = (0..self.fft_size)
+            .map(|i| Complex::new(frame.get(i).copied().unwrap_or(0.0) * self.window[i], 0.0))
+            .collect();
+
+        self.fft.process(&mut buffer);
+
+        buffer[..self.bin_count()]
+            .iter()
+            .map(|c| c.norm_sqr() * self.power_scale)
+            .collect()
+    }
+
+    /// Power spectra of successive hop-spaced frames. A signal shorter than one frame
+    /// still yields one (zero-padded) spectrum.
+    pub fn spectrogram(&self, samples: &[f32]) -> Vec<Vec<f32>> {
+        if samples.len() <= self.fft_size {
+            return vec![self.power_spectrum(samples)];
+        }
+
+        (0..=samples.len() - self.fft_size)
+            .step_by(self.hop)
+            .map(|start| self.power_spectrum(&samples[start..start + self.fft_size]))
+            .collect()
+    }
+
+    /// Mean power spectrum over all frames (Welch's method)
+    pub fn average_power_spectrum(&self, samples: &[f32]) -> Vec<f32> {
+        let frames = self.spectrogram(samples);
+        let mut average = vec![0.0; self.bin_count()];
+        for frame in &frames {
+            for (avg, &p) in average.iter_mut().zip(frame) {
+                *avg += p;
+            }
+        }
+        for avg in &mut average {
+            *avg /= frames.len() as f32;
+        }
+        average
+    }
+}
+
+/// Triangular filters evenly spaced on the mel scale
+pub struct MelFilterbank {
+    filters: Vec<Vec<(usize, f32)>>,   // Per band: (bin, weight) pairs
+}
+
+impl MelFilterbank {
+    pub fn new(bands: usize, front_end: &SpectralFrontEnd, min_hz: f32, max_hz: f32) -> Self {
+        let max_hz = max_hz.min(front_end.sample_rate as f32 / 2.0);
+        let (min_mel, max_mel) = (hz_to_mel(min_hz), hz_to_mel(max_hz));
+
+        // bands + 2 edge frequencies: each filter rises from edge i to i+1 and falls to i+2
+        let edges: Vec<f32> = (0..bands + 2)
+            .map(|i| mel_to_hz(min_mel + (max_mel - min_mel) * i as f32 / (bands + 1) as f32))
+            .collect();
+
+        let filters = (0..bands)
+            .map(|band| {
+                let (lo, center, hi) = (edges[band], edges[band + 1], edges[band + 2]);
+                let mut weights: Vec<(usize, f32)> = (0..front_end.bin_count())
+                    .filter_map(|bin| {
+                        let f = front_end.bin_frequency(bin);
+                        let w = if f > lo && f <= center {
+                            (f - lo) / (center - lo)
+                        } else if f > center && f < hi {
+                            (hi - f) / (hi - center)
+                        } else {
+                            0.0
+                        };
+                        (w > 0.0).then_some((bin, w))
+                    })
+                    .collect();
+
+                // Low bands can be narrower than one bin - use the nearest bin instead of nothing
+                if weights.is_empty() {
+                    let nearest = (center * front_end.fft_size as f32 / front_end.sample_rate as f32).round() as usize;
+                    weights.push((nearest.min(front_end.bin_count() - 1), 1.0));
+                }
+                weights
+            })
+            .collect();
+
+        MelFilterbank { filters }
+    }
+
+    pub fn bands(&self) -> usize {
+        self.filters.len()
+    }
+
+    /// Energy per mel band of a power spectrum
+    pub fn apply(&self, power: &[f32]) -> Vec<f32> {
+        self.filters
+            .iter()
+            .map(|filter| filter.iter().map(|&(bin, w)| power[bin] * w).sum())
+            .collect()
+    }
+}
+
+/// Mel-frequency cepstral coefficients: orthonormal DCT-II of log mel energies
+pub fn mfcc(mel_energies: &[f32], coefficients: usize) -> Vec<f32> {
+    let n = mel_energies.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let log_energies: Vec<f32> = mel_energies.iter().map(|&e| (e + 1e-10).ln()).collect();
+
+    (0..coefficients.min(n))
+        .map(|k| {
+            let scale = if k == 0 { (1.0 / n as f32).sqrt() } else { (2.0 / n as f32).sqrt() };
+            scale * log_energies.iter()
+                .enumerate()
+                .map(|(i, &e)| e * (PI * k as f32 * (i as f32 + 0.5) / n as f32).cos())
+                .sum::<f32>()
+        })
+        .collect()
+}
+
+/// Power to decibels, floored at -120 dB
+pub fn power_to_db(power: f32) -> f32 {
+    10.0 * power.max(1e-12).log10()
+}
+
+pub fn hz_to_mel(hz: f32) -> f32 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+pub fn mel_to_hz(mel: f32) -> f32 {
+    700.0 * (10f32.powf(mel / 2595.0) - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 44100;
+
+    #[test]
+    fn windows_have_the_textbook_shape() {
+        let hann = WindowFunction::Hann.coefficients(9);
+        assert!(hann[0].abs() < 1e-6 && hann[8].abs() < 1e-6);
+        assert!((hann[4] - 1.0).abs() < 1e-6);
+
+        let hamming = WindowFunction::Hamming.coefficients(9);
+        assert!((hamming[0] - 0.08).abs() < 1e-6 && (hamming[4] - 1.0).abs() < 1e-6);
+        for window in [&hann, &hamming] {
+            assert!((0..9).all(|n| (window[n] - window[8 - n]).abs() < 1e-6), "symmetric");
+        }
+
+        assert_eq!(WindowFunction::Rectangular.coefficients(4), [1.0; 4]);
+        assert_eq!(WindowFunction::Hann.coefficients(1).len(), 1);
+    }
+
+    #[test]
+    fn mel_and_hz_round_trip() {
+        for hz in [0.0, 60.0, 700.0, 1000.0, 8000.0, 22050.0] {
+            assert!((mel_to_hz(hz_to_mel(hz)) - hz).abs() < hz.max(1.0) * 1e-4, "{} Hz", hz);
+        }
+        // 1000 Hz is 1000 mel by construction
+        assert!((hz_to_mel(1000.0) - 1000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn filterbank_weights_are_non_negative_and_cover_every_band() {
+        let front_end = SpectralFrontEnd::default_for(SAMPLE_RATE);
+        let filterbank = MelFilterbank::new(40, &front_end, 20.0, 8000.0);
+        assert_eq!(filterbank.bands(), 40);
+
+        for filter in &filterbank.filters {
+            assert!(!filter.is_empty());
+            assert!(filter.iter().all(|&(bin, w)| w > 0.0 && w <= 1.0 && bin < front_end.bin_count()));
+        }
+
+        // Every band answers to a spectrum with energy everywhere
+        let energies = filterbank.apply(&vec![1.0; front_end.bin_count()]);
+        assert!(energies.iter().all(|&e| e > 0.0));
+    }
+
+    #[test]
+    fn sine_peaks_in_its_bin_and_mel_band() {
+        let front_end = SpectralFrontEnd::default_for(SAMPLE_RATE);
+        let bin = 46;
+        let frequency = front_end.bin_frequency(bin);
+        let samples: Vec<f32> = (0..front_end.fft_size)
+            .map(|i| (2.0 * PI * frequency * i as f32 / SAMPLE_RATE as f32).sin())
+            .collect();
+
+        let power = front_end.power_spectrum(&samples);
+        let peak = (0..power.len()).max_by(|&a, &b| power[a].total_cmp(&power[b])).unwrap();
+        assert_eq!(peak, bin);
+        assert!((power_to_db(power[bin])).abs() < 0.5, "full-scale sine at {} dB", power_to_db(power[bin]));
+
+        let filterbank = MelFilterbank::new(40, &front_end, 20.0, 8000.0);
+        let weight_at_bin = |band: usize| {
+            filterbank.filters[band].iter().find(|&&(b, _)| b == bin).map_or(0.0, |&(_, w)| w)
+        };
+        let expected = (0..40).max_by(|&a, &b| weight_at_bin(a).total_cmp(&weight_at_bin(b))).unwrap();
+        let energies = filterbank.apply(&power);
+        let loudest = (0..40).max_by(|&a, &b| energies[a].total_cmp(&energies[b])).unwrap();
+        assert_eq!(loudest, expected);
+    }
+
+    #[test]
+    fn flat_mel_energies_have_no_cepstrum_beyond_c0() {
+        let coefficients = mfcc(&[0.5; 26], 13);
+        assert_eq!(coefficients.len(), 13);
+        assert!(coefficients[0].abs() > 1.0);
+        assert!(coefficients[1..].iter().all(|c| c.abs() < 1e-4), "{:?}", coefficients);
+
+        assert!(mfcc(&[], 13).is_empty());
+        assert_eq!(mfcc(&[1.0; 4], 13).len(), 4);
+    }
+}
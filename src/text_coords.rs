@@ -0,0 +1,218 @@
+// Where text lands in the Mandelbrot plane
+// A plain hash of the string (related strings scatter like unrelated ones), the same hash
+// kept to the set's boundary, or a projection of its character n-grams so similar
+// spellings land close together.
+
+use std::fmt;
+use std::str::FromStr;
+use bincode::{Encode, Decode};
+use serde::{Serialize, Deserialize};
+
+use crate::fractal::{
+    MandelbrotCoord, hash_to_mandelbrot_coord_with, boundary_mandelbrot_coord_with, plane_to_mandelbrot_coord,
+    in_boundary_band, julia_fingerprint_from_mandelbrot, hamming_distance
+};
+use crate::hashing::{CoordHasher, SipHash13};
+use crate::memory::ConceptTrajectory;
+
+/// Character n-gram lengths, with how much each counts. With only two output dimensions,
+/// the letters a string is made of do most to keep one-edit variants close; the longer
+/// n-grams add letter order, so anagrams still land apart.
+const NGRAMS: [(usize, f64); 3] = [(1, 2.0), (2, 1.0), (3, 0.5)];
+/// Marks the start and end of the string, so "cat" and "act" don't share their edges
+const BOUNDARY: char = '\u{2}';
+/// Projected distance that spans about half of the region on each axis
+const SPREAD: f64 = 0.5;
+
+/// How a mind turns strings into coordinates - saved with the mind
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Encode, Decode, Serialize, Deserialize)]
+pub enum TextCoordMapper {
+    /// Hash the whole string with the mind's CoordHasher: any change lands somewhere unrelated
+    #[default]
+    Hashed,
+    /// SimHash-style random projection of character n-gram counts, kept continuous rather
+    /// than reduced to sign bits: strings one edit apart land near each other
+    NGram,
+    /// Hashed, but redrawn until the point is in the stability band along the set's boundary,
+    /// so fingerprints of unrelated strings stop merging (see boundary_mandelbrot_coord_with)
+    Boundary,
+}
+
+impl TextCoordMapper {
+    pub const ALL: [TextCoordMapper; 3] = [TextCoordMapper::Hashed, TextCoordMapper::NGram, TextCoordMapper::Boundary];
+
+    pub fn coord<H: CoordHasher + ?Sized>(&self, hasher: &H, input: &str) -> MandelbrotCoord {
+        match self {
+            TextCoordMapper::Hashed => hash_to_mandelbrot_coord_with(hasher, input),
+            TextCoordMapper::NGram => ngram_to_mandelbrot_coord(input),
+            TextCoordMapper::Boundary => boundary_mandelbrot_coord_with(hasher, input),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TextCoordMapper::Hashed => "hashed",
+            TextCoordMapper::NGram => "ngram",
+            TextCoordMapper::Boundary => "boundary",
+        }
+    }
+}
+
+impl fmt::Display for TextCoordMapper {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TextCoordMapper {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hashed" => Ok(TextCoordMapper::Hashed),
+            "ngram" => Ok(TextCoordMapper::NGram),
+            "boundary" => Ok(TextCoordMapper::Boundary),
+            other => Err(format!("unknown text coordinate mapper '{}' (expected hashed, ngram or boundary)", other)),
+        }
+    }
+}
+
+/// Every n-gram of the padded string pulls the point along its own fixed Gaussian
+/// direction (derived from its hash). The sum is divided by the length of the weighted
+/// count vector, so each axis is ~N(0, 1) whatever the string length, and two strings
+/// are as far apart as the angle between their n-gram vectors.
+pub fn ngram_to_mandelbrot_coord(input: &str) -> MandelbrotCoord {
+    let mut chars = vec![BOUNDARY];
+    chars.extend(input.chars());
+    chars.push(BOUNDARY);
+
+    let mut counts: Vec<(String, f64)> = Vec::new();
+    for &(n, weight) in &NGRAMS {
+        for gram in chars.windows(n) {
+            let gram: String = gram.iter().collect();
+            match counts.iter_mut().find(|(g, _)| *g == gram) {
+                Some((_, count)) => *count += weight,
+                None => counts.push((gram, weight)),
+            }
+        }
+    }
+
+    let norm = counts.iter().map(|(_, c)| c * c).sum::<f64>().sqrt();
+    let (mut x, mut y) = (0.0, 0.0);
+    for (gram, count) in &counts {
+        let (gx, gy) = gaussian_pair(SipHash13.hash_str(gram));
+        x += gx * count;
+        y += gy * count;
+    }
+
+    plane_to_mandelbrot_coord(x / norm / SPREAD, y / norm / SPREAD)
+}
+
+/// Two independent standard normals from the two halves of a hash (Box-Muller)
+fn gaussian_pair(hash: u64) -> (f64, f64) {
+    let u1 = ((hash >> 32) as f64 + 1.0) / (u32::MAX as f64 + 2.0);
+    let u2 = (hash as u32) as f64 / (u32::MAX as f64 + 1.0);
+    let radius = (-2.0 * u1.ln()).sqrt();
+    let angle = 2.0 * std::f64::consts::PI * u2;
+    (radius * angle.cos(), radius * angle.sin())
+}
+
+/// How a mapper's coordinates fare as symbols
+#[derive(Clone, Copy, Debug)]
+pub struct CollisionStats {
+    pub samples: usize,
+    pub merged: usize,          // Fingerprints within the merge threshold of an earlier one
+    pub in_band: usize,         // Coordinates in the boundary band (see in_boundary_band)
+    pub crowding: f64,          // Mean share of strength trajectories at two of the coordinates feel of each other
+}
+
+impl CollisionStats {
+    /// Share of samples that would have merged into an earlier symbol
+    pub fn collision_rate(&self) -> f64 {
+        if self.samples == 0 { 0.0 } else { self.merged as f64 / self.samples as f64 }
+    }
+}
+
+/// Map each input and count how many Julia fingerprints fall within merge_threshold bits
+/// of an earlier one - the same test store_symbol_at_coord merges on - and how much the
+/// coordinates crowd each other
+pub fn collision_stats<H: CoordHasher + ?Sized>(
+    mapper: TextCoordMapper,
+    hasher: &H,
+    inputs: &[String],
+    scale: u32,
+    merge_threshold: u32,
+) -> CollisionStats {
+    let mut fingerprints = Vec::with_capacity(inputs.len());
+    let mut coords: Vec<MandelbrotCoord> = Vec::with_capacity(inputs.len());
+    let mut stats = CollisionStats { samples: inputs.len(), merged: 0, in_band: 0, crowding: 0.0 };
+    let mut falloff = 0.0;
+
+    for input in inputs {
+        let coord = mapper.coord(hasher, input);
+        if in_boundary_band(coord) {
+            stats.in_band += 1;
+        }
+        falloff += coords.iter().map(|&earlier| ConceptTrajectory::falloff(ConceptTrajectory::coord_distance(coord, earlier))).sum::<f64>();
+        coords.push(coord);
+
+        let pattern = julia_fingerprint_from_mandelbrot(coord, scale);
+        if fingerprints.iter().any(|earlier| hamming_distance(&pattern, earlier) < merge_threshold) {
+            stats.merged += 1;
+        }
+        fingerprints.push(pattern);
+    }
+
+    let pairs = inputs.len() * inputs.len().saturating_sub(1) / 2;
+    if pairs > 0 {
+        stats.crowding = falloff / pairs as f64;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+    use crate::rng::MindRng;
+
+    fn random_words(rng: &mut MindRng, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|_| (0..rng.gen_range(8..14)).map(|_| rng.gen_range(b'a'..=b'z') as char).collect())
+            .collect()
+    }
+
+    fn ngram_distance(a: &str, b: &str) -> f64 {
+        ConceptTrajectory::coord_distance(ngram_to_mandelbrot_coord(a), ngram_to_mandelbrot_coord(b))
+    }
+
+    #[test]
+    fn ngram_keeps_one_edit_variants_closer_than_random_pairs() {
+        let mut rng = MindRng::from_seed(3);
+        let words = random_words(&mut rng, 200);
+
+        let mut edited = 0.0;
+        let mut unrelated = 0.0;
+        for (word, other) in words.iter().zip(words.iter().skip(1)) {
+            let mut chars: Vec<char> = word.chars().collect();
+            let at = rng.gen_range(0..chars.len());
+            chars[at] = if chars[at] == 'z' { 'a' } else { (chars[at] as u8 + 1) as char };
+            edited += ngram_distance(word, &chars.into_iter().collect::<String>());
+            unrelated += ngram_distance(word, other);
+        }
+
+        assert!(edited < 0.5 * unrelated, "one edit apart: {edited:.2} total, random pairs: {unrelated:.2} total");
+    }
+
+    #[test]
+    fn boundary_mapper_merges_far_less_than_hashed_without_crowding() {
+        let words = random_words(&mut MindRng::from_seed(9), 120);
+        let stats = |mapper| collision_stats(mapper, &SipHash13, &words, 4096, 8);
+        let (hashed, boundary) = (stats(TextCoordMapper::Hashed), stats(TextCoordMapper::Boundary));
+
+        assert!(boundary.merged * 4 < hashed.merged, "boundary merged {}, hashed {} of {}", boundary.merged, hashed.merged, words.len());
+        assert_eq!(boundary.in_band, words.len());
+        // Spread along the whole boundary, not packed where trajectories would all pull on each other
+        assert!(boundary.crowding < 2.5 * hashed.crowding, "crowding: boundary {:.3}, hashed {:.3}", boundary.crowding, hashed.crowding);
+    }
+}
@@ -0,0 +1,278 @@
+// Texture features for image encoding
+// Rotation-invariant local binary patterns and a small Gabor filter bank,
+// computed in floating point per grid cell and quantized into symbols.
+
+use image::{GrayImage, imageops};
+use std::f32::consts::PI;
+use std::ops::Range;
+
+/// Larger images are scaled down to this long side before analysis
+const MAX_SIDE: f32 = 256.0;
+/// Smaller images are scaled up so every cell has at least this many pixels across
+const MIN_CELL: f32 = 8.0;
+
+/// Gabor wavelengths in pixels: fine and coarse
+const WAVELENGTHS: [f32; 2] = [4.0, 8.0];
+const ORIENTATIONS: usize = 4;
+/// Gabor responses are sampled on every STRIDE-th pixel
+const STRIDE: usize = 2;
+
+/// Number of rotation-invariant uniform LBP codes (8 neighbours): 0..=8 set bits, plus "non-uniform"
+pub const LBP_CODES: usize = 10;
+
+/// Texture description of one cell
+#[derive(Clone, Debug)]
+pub struct CellTexture {
+    pub lbp: [f32; LBP_CODES],                           // Normalized histogram of LBP codes
+    pub gabor: [[f32; ORIENTATIONS]; WAVELENGTHS.len()], // Mean response magnitude per [wavelength][orientation]
+}
+
+impl CellTexture {
+    /// Symbol for the cell: energy level 0-3, stripe direction (`- / | \`, or `o` when
+    /// isotropic), `f`ine or `c`oarse scale, and the most common LBP code (9 = noisy)
+    /// (e.g., "2|f4" for vertical stripes, "0of8" for a flat patch)
+    pub fn symbol(&self) -> String {
+        let per_orientation: Vec<f32> = (0..ORIENTATIONS)
+            .map(|o| self.gabor.iter().map(|scale| scale[o]).sum())
+            .collect();
+        let total: f32 = per_orientation.iter().sum();
+        let mean = total / ORIENTATIONS as f32;
+
+        let energy = match total {
+            e if e < 0.01 => 0,
+            e if e < 0.05 => 1,
+            e if e < 0.2 => 2,
+            _ => 3,
+        };
+
+        let (strongest, &peak) = per_orientation.iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap_or((0, &0.0));
+        // Filter orientation is the direction of the wave, stripes run across it (y points down)
+        let direction = if energy == 0 || peak < 1.5 * mean {
+            'o'
+        } else {
+            ['|', '/', '-', '\\'][strongest]
+        };
+
+        let fine: f32 = self.gabor[0].iter().sum();
+        let coarse: f32 = self.gabor[1].iter().sum();
+        let scale = if energy == 0 || fine >= coarse { 'f' } else { 'c' };
+
+        let code = self.lbp.iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(code, _)| code)
+            .unwrap_or(0);
+
+        format!("{}{}{}{}", energy, direction, scale, code)
+    }
+}
+
+/// Describe each cell of a grid_size x grid_size grid, row by row. Large images are
+/// scaled down first and tiny ones up, so any grid size works on any image.
+pub fn cell_textures(gray: &GrayImage, grid_size: usize) -> Vec<CellTexture> {
+    if grid_size == 0 || gray.width() == 0 || gray.height() == 0 {
+        return Vec::new();
+    }
+
+    let (w, h) = (gray.width() as f32, gray.height() as f32);
+    let scale = (MAX_SIDE / w.max(h)).min(1.0);
+    let min_side = grid_size as f32 * MIN_CELL;
+    let width = ((w * scale).round().max(min_side)) as usize;
+    let height = ((h * scale).round().max(min_side)) as usize;
+
+    let resized = imageops::resize(gray, width as u32, height as u32, imageops::FilterType::Triangle);
+    let pixels: Vec<f32> = resized.pixels().map(|p| p[0] as f32 / 255.0).collect();
+    let bank = GaborBank::new();
+
+    let mut cells = Vec::with_capacity(grid_size * grid_size);
+    for gy in 0..grid_size {
+        for gx in 0..grid_size {
+            let xs = gx * width / grid_size..(gx + 1) * width / grid_size;
+            let ys = gy * height / grid_size..(gy + 1) * height / grid_size;
+            cells.push(CellTexture {
+                lbp: lbp_histogram(&pixels, width, height, xs.clone(), ys.clone()),
+                gabor: bank.mean_responses(&pixels, width, height, xs, ys),
+            });
+        }
+    }
+    cells
+}
+
+/// Normalized histogram of rotation-invariant uniform LBP codes over a region.
+/// Code n (0..=8) = a uniform pattern with n neighbours at least as bright as the centre.
+pub fn lbp_histogram(pixels: &[f32], width: usize, height: usize, xs: Range<usize>, ys: Range<usize>) -> [f32; LBP_CODES] {
+    const NEIGHBOURS: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+    // Tolerance so flat regions read as all-bright rather than as sensor noise
+    const TOLERANCE: f32 = 2.0 / 255.0;
+
+    let at = |x: isize, y: isize| {
+        pixels[y.clamp(0, height as isize - 1) as usize * width + x.clamp(0, width as isize - 1) as usize]
+    };
+
+    let mut histogram = [0.0f32; LBP_CODES];
+    let mut count = 0;
+    for y in ys.start..ys.end.min(height) {
+        for x in xs.start..xs.end.min(width) {
+            let centre = pixels[y * width + x];
+            let bits: Vec<bool> = NEIGHBOURS.iter()
+                .map(|&(dx, dy)| at(x as isize + dx, y as isize + dy) >= centre - TOLERANCE)
+                .collect();
+            let transitions = (0..8).filter(|&i| bits[i] != bits[(i + 1) % 8]).count();
+            let code = if transitions <= 2 { bits.iter().filter(|&&b| b).count() } else { LBP_CODES - 1 };
+            histogram[code] += 1.0;
+            count += 1;
+        }
+    }
+
+    if count > 0 {
+        for bin in &mut histogram {
+            *bin /= count as f32;
+        }
+    }
+    histogram
+}
+
+/// (dx, dy, real, imaginary) taps of one complex filter
+type GaborKernel = Vec<(isize, isize, f32, f32)>;
+
+/// Complex Gabor filters at each wavelength and orientation
+pub struct GaborBank {
+    kernels: Vec<Vec<GaborKernel>>,   // Per [wavelength][orientation]
+}
+
+impl GaborBank {
+    pub fn new() -> Self {
+        let kernels = WAVELENGTHS.iter()
+            .map(|&wavelength| {
+                (0..ORIENTATIONS)
+                    .map(|o| gabor_kernel(wavelength, o as f32 * PI / ORIENTATIONS as f32))
+                    .collect()
+            })
+            .collect();
+        GaborBank { kernels }
+    }
+
+    /// Mean response magnitude of every filter over a region
+    pub fn mean_responses(&self, pixels: &[f32], width: usize, height: usize, xs: Range<usize>, ys: Range<usize>) -> [[f32; ORIENTATIONS]; WAVELENGTHS.len()] {
+        let at = |x: isize, y: isize| {
+            pixels[y.clamp(0, height as isize - 1) as usize * width + x.clamp(0, width as isize - 1) as usize]
+        };
+
+        let mut means = [[0.0f32; ORIENTATIONS]; WAVELENGTHS.len()];
+        for (scale, kernels) in self.kernels.iter().enumerate() {
+            for (orientation, kernel) in kernels.iter().enumerate() {
+                let mut sum = 0.0;
+                let mut count = 0;
+                for y in (ys.start..ys.end.min(height)).step_by(STRIDE) {
+                    for x in (xs.start..xs.end.min(width)).step_by(STRIDE) {
+                        let (mut re, mut im) = (0.0, 0.0);
+                        for &(dx, dy, kr, ki) in kernel {
+                            let p = at(x as isize + dx, y as isize + dy);
+                            re += p * kr;
+                            im += p * ki;
+                        }
+                        sum += re.hypot(im);
+                        count += 1;
+                    }
+                }
+                means[scale][orientation] = if count > 0 { sum / count as f32 } else { 0.0 };
+            }
+        }
+        means
+    }
+}
+
+impl Default for GaborBank {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One octave bandwidth, aspect 0.5. The real part is made zero-mean so flat areas give
+/// no response, and both parts are scaled so a full-contrast grating gives ~0.5.
+fn gabor_kernel(wavelength: f32, theta: f32) -> GaborKernel {
+    let sigma = 0.56 * wavelength;
+    let gamma = 0.5;
+    let radius = (2.5 * sigma).ceil() as isize;
+    let (sin, cos) = theta.sin_cos();
+
+    let mut taps: GaborKernel = Vec::new();
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            let along = dx as f32 * cos + dy as f32 * sin;
+            let across = -(dx as f32) * sin + dy as f32 * cos;
+            let envelope = (-(along * along + gamma * gamma * across * across) / (2.0 * sigma * sigma)).exp();
+            if envelope < 0.01 {
+                continue;
+            }
+            let phase = 2.0 * PI * along / wavelength;
+            taps.push((dx, dy, envelope * phase.cos(), envelope * phase.sin()));
+        }
+    }
+
+    let envelope_sum: f32 = taps.iter().map(|t| t.2.hypot(t.3)).sum();
+    let real_mean = taps.iter().map(|t| t.2).sum::<f32>() / taps.len() as f32;
+    let scale = 2.0 / envelope_sum.max(f32::EPSILON);
+    for tap in &mut taps {
+        tap.2 = (tap.2 - real_mean) * scale;
+        tap.3 *= scale;
+    }
+    taps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Luma};
+    use crate::image::{encode_image_data, ImageEncoding};
+
+    /// Sinusoidal grating with the given period, varying along x (vertical stripes) or y
+    fn grating(period: f32, along_x: bool) -> GrayImage {
+        GrayImage::from_fn(64, 64, |x, y| {
+            let t = if along_x { x } else { y } as f32;
+            Luma([(127.5 + 127.5 * (2.0 * PI * t / period).sin()) as u8])
+        })
+    }
+
+    fn symbol(gray: &GrayImage) -> String {
+        cell_textures(gray, 1)[0].symbol()
+    }
+
+    #[test]
+    fn grid_larger_than_the_image_is_scaled_up() {
+        let tiny = GrayImage::from_fn(3, 3, |x, y| Luma([((x + y) * 40) as u8]));
+        assert_eq!(cell_textures(&tiny, 16).len(), 256);
+
+        let encoded = encode_image_data(&DynamicImage::ImageLuma8(tiny), ImageEncoding::TexturePattern { grid_size: 16 });
+        assert_eq!(encoded.trim_start_matches("texture:").split(',').count(), 256);
+    }
+
+    #[test]
+    fn empty_image_has_no_cells() {
+        let empty = GrayImage::new(0, 0);
+        assert!(cell_textures(&empty, 4).is_empty());
+        assert_eq!(encode_image_data(&DynamicImage::ImageLuma8(empty), ImageEncoding::TexturePattern { grid_size: 4 }), "texture:");
+    }
+
+    #[test]
+    fn large_flat_image_reads_as_flat() {
+        // Wider than u16 and more pixels than fit a u16 product
+        let flat = GrayImage::from_pixel(70_000, 40, Luma([128]));
+        let cells = cell_textures(&flat, 4);
+        assert_eq!(cells.len(), 16);
+        assert!(cells.iter().all(|cell| cell.symbol() == "0of8"));
+    }
+
+    #[test]
+    fn stripe_direction_and_scale_are_told_apart() {
+        let vertical = symbol(&grating(4.0, true));
+        let horizontal = symbol(&grating(4.0, false));
+        assert_eq!(&vertical[1..3], "|f");
+        assert_eq!(&horizontal[1..3], "-f");
+
+        assert_eq!(&symbol(&grating(16.0, true))[1..3], "|c");
+    }
+}
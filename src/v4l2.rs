@@ -0,0 +1,343 @@
+// V4L2 webcam capture (Linux, `v4l2` feature)
+// Memory-mapped streaming straight over ioctl - no libv4l or bindgen/libclang needed.
+// Works with any /dev/video* capture node, including v4l2loopback devices.
+
+use std::ffi::CString;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use crate::frame_source::{Frame, FrameSource};
+use crate::video::ycbcr_to_rgb;
+
+const BUFFER_COUNT: u32 = 4;
+const FRAME_TIMEOUT_MS: i32 = 2000;
+
+const BUF_TYPE_VIDEO_CAPTURE: u32 = 1;
+const MEMORY_MMAP: u32 = 1;
+const FIELD_ANY: u32 = 0;
+
+const CAP_VIDEO_CAPTURE: u32 = 0x0000_0001;
+const CAP_STREAMING: u32 = 0x0400_0000;
+const CAP_DEVICE_CAPS: u32 = 0x8000_0000;
+
+const PIX_FMT_YUYV: u32 = fourcc(b"YUYV");
+const PIX_FMT_RGB24: u32 = fourcc(b"RGB3");
+const PIX_FMT_MJPEG: u32 = fourcc(b"MJPG");
+
+const fn fourcc(code: &[u8; 4]) -> u32 {
+    u32::from_le_bytes(*code)
+}
+
+// Kernel structs from <linux/videodev2.h>, only the fields we touch are meaningful
+
+#[repr(C)]
+struct Capability {
+    driver: [u8; 16],
+    card: [u8; 32],
+    bus_info: [u8; 32],
+    version: u32,
+    capabilities: u32,
+    device_caps: u32,
+    reserved: [u32; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct PixFormat {
+    width: u32,
+    height: u32,
+    pixelformat: u32,
+    field: u32,
+    bytesperline: u32,
+    sizeimage: u32,
+    colorspace: u32,
+    priv_: u32,
+    flags: u32,
+    encoding: u32,
+    quantization: u32,
+    xfer_func: u32,
+}
+
+// 200 bytes, pointer-aligned like the kernel's union (its window variant holds pointers)
+#[repr(C)]
+union FormatUnion {
+    pix: PixFormat,
+    raw: [u64; 25],
+}
+
+#[repr(C)]
+struct Format {
+    type_: u32,
+    fmt: FormatUnion,
+}
+
+#[repr(C)]
+struct RequestBuffers {
+    count: u32,
+    type_: u32,
+    memory: u32,
+    capabilities: u32,
+    flags: u8,
+    reserved: [u8; 3],
+}
+
+#[repr(C)]
+struct Timecode {
+    type_: u32,
+    flags: u32,
+    frames: u8,
+    seconds: u8,
+    minutes: u8,
+    hours: u8,
+    userbits: [u8; 4],
+}
+
+#[repr(C)]
+union BufferLocation {
+    offset: u32,
+    userptr: libc::c_ulong,
+    planes: *mut libc::c_void,
+    fd: i32,
+}
+
+#[repr(C)]
+struct Buffer {
+    index: u32,
+    type_: u32,
+    bytesused: u32,
+    flags: u32,
+    field: u32,
+    timestamp: libc::timeval,
+    timecode: Timecode,
+    sequence: u32,
+    memory: u32,
+    m: BufferLocation,
+    length: u32,
+    reserved2: u32,
+    request_fd: i32,
+}
+
+// _IOC encoding for the 'V' ioctls
+const IOC_WRITE: u32 = 1;
+const IOC_READ: u32 = 2;
+
+const fn ioc(dir: u32, nr: u32, size: usize) -> u32 {
+    (dir << 30) | ((size as u32) << 16) | ((b'V' as u32) << 8) | nr
+}
+
+const VIDIOC_QUERYCAP: u32 = ioc(IOC_READ, 0, size_of::<Capability>());
+const VIDIOC_S_FMT: u32 = ioc(IOC_READ | IOC_WRITE, 5, size_of::<Format>());
+const VIDIOC_REQBUFS: u32 = ioc(IOC_READ | IOC_WRITE, 8, size_of::<RequestBuffers>());
+const VIDIOC_QUERYBUF: u32 = ioc(IOC_READ | IOC_WRITE, 9, size_of::<Buffer>());
+const VIDIOC_QBUF: u32 = ioc(IOC_READ | IOC_WRITE, 15, size_of::<Buffer>());
+const VIDIOC_DQBUF: u32 = ioc(IOC_READ | IOC_WRITE, 17, size_of::<Buffer>());
+const VIDIOC_STREAMON: u32 = ioc(IOC_WRITE, 18, size_of::<libc::c_int>());
+const VIDIOC_STREAMOFF: u32 = ioc(IOC_WRITE, 19, size_of::<libc::c_int>());
+
+// Struct layouts must match the kernel's - check against the request numbers it defines
+#[cfg(target_pointer_width = "64")]
+const _: () = {
+    assert!(VIDIOC_QUERYCAP == 0x8068_5600);
+    assert!(VIDIOC_S_FMT == 0xc0d0_5605);
+    assert!(VIDIOC_REQBUFS == 0xc014_5608);
+    assert!(VIDIOC_QUERYBUF == 0xc058_5609);
+    assert!(VIDIOC_DQBUF == 0xc058_5611);
+    assert!(VIDIOC_STREAMON == 0x4004_5612);
+};
+
+fn xioctl<T>(fd: &OwnedFd, request: u32, arg: &mut T) -> io::Result<()> {
+    loop {
+        // SAFETY: every request above is paired with the struct type it was sized from
+        let ret = unsafe { libc::ioctl(fd.as_raw_fd(), request as _, arg as *mut T) };
+        if ret != -1 {
+            return Ok(());
+        }
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+// SAFETY (for the zeroed() calls below): all kernel structs here are plain integers/unions
+fn zeroed<T>() -> T {
+    unsafe { std::mem::zeroed() }
+}
+
+struct MappedBuffer {
+    ptr: *mut libc::c_void,
+    length: usize,
+}
+
+/// A streaming V4L2 capture device, converted to RGB24 frame by frame
+pub struct V4l2Camera {
+    fd: OwnedFd,
+    width: usize,
+    height: usize,
+    stride: usize,
+    pixel_format: u32,
+    buffers: Vec<MappedBuffer>,
+}
+
+// The mappings are owned exclusively by this struct
+unsafe impl Send for V4l2Camera {}
+
+impl V4l2Camera {
+    /// Open a capture device asking for width x height - the driver may pick the nearest size it supports
+    pub fn open(path: &str, width: usize, height: usize) -> Result<Self, Box<dyn std::error::Error>> {
+        let c_path = CString::new(path)?;
+        let raw_fd = unsafe { libc::open(c_path.as_ptr(), libc::O_RDWR | libc::O_CLOEXEC) };
+        if raw_fd < 0 {
+            return Err(format!("{}: {}", path, io::Error::last_os_error()).into());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(raw_fd) };
+
+        let mut cap: Capability = zeroed();
+        xioctl(&fd, VIDIOC_QUERYCAP, &mut cap)?;
+        let caps = if cap.capabilities & CAP_DEVICE_CAPS != 0 { cap.device_caps } else { cap.capabilities };
+        if caps & CAP_VIDEO_CAPTURE == 0 || caps & CAP_STREAMING == 0 {
+            return Err(format!("{} is not a streaming video capture device", path).into());
+        }
+
+        // Ask for YUYV (every UVC webcam has it); accept RGB24 or MJPEG if the driver insists
+        let mut requested: PixFormat = zeroed();
+        requested.width = width as u32;
+        requested.height = height as u32;
+        requested.pixelformat = PIX_FMT_YUYV;
+        requested.field = FIELD_ANY;
+
+        let mut format: Format = zeroed();
+        format.type_ = BUF_TYPE_VIDEO_CAPTURE;
+        format.fmt.pix = requested;
+        xioctl(&fd, VIDIOC_S_FMT, &mut format)?;
+        let pix = unsafe { format.fmt.pix };
+
+        if ![PIX_FMT_YUYV, PIX_FMT_RGB24, PIX_FMT_MJPEG].contains(&pix.pixelformat) {
+            let code = pix.pixelformat.to_le_bytes();
+            return Err(format!("unsupported pixel format {}", String::from_utf8_lossy(&code)).into());
+        }
+
+        let mut request: RequestBuffers = zeroed();
+        request.count = BUFFER_COUNT;
+        request.type_ = BUF_TYPE_VIDEO_CAPTURE;
+        request.memory = MEMORY_MMAP;
+        xioctl(&fd, VIDIOC_REQBUFS, &mut request)?;
+        if request.count == 0 {
+            return Err("driver granted no capture buffers".into());
+        }
+
+        let mut camera = V4l2Camera {
+            fd,
+            width: pix.width as usize,
+            height: pix.height as usize,
+            stride: pix.bytesperline as usize,
+            pixel_format: pix.pixelformat,
+            buffers: Vec::with_capacity(request.count as usize),
+        };
+
+        for index in 0..request.count {
+            let mut buffer = camera.empty_buffer(index);
+            xioctl(&camera.fd, VIDIOC_QUERYBUF, &mut buffer)?;
+
+            let length = buffer.length as usize;
+            let ptr = unsafe {
+                libc::mmap(
+                    std::ptr::null_mut(),
+                    length,
+                    libc::PROT_READ | libc::PROT_WRITE,
+                    libc::MAP_SHARED,
+                    camera.fd.as_raw_fd(),
+                    buffer.m.offset as libc::off_t,
+                )
+            };
+            if ptr == libc::MAP_FAILED {
+                return Err(io::Error::last_os_error().into());
+            }
+            camera.buffers.push(MappedBuffer { ptr, length });
+
+            xioctl(&camera.fd, VIDIOC_QBUF, &mut buffer)?;
+        }
+
+        let mut buf_type = BUF_TYPE_VIDEO_CAPTURE as libc::c_int;
+        xioctl(&camera.fd, VIDIOC_STREAMON, &mut buf_type)?;
+
+        Ok(camera)
+    }
+
+    fn empty_buffer(&self, index: u32) -> Buffer {
+        let mut buffer: Buffer = zeroed();
+        buffer.index = index;
+        buffer.type_ = BUF_TYPE_VIDEO_CAPTURE;
+        buffer.memory = MEMORY_MMAP;
+        buffer
+    }
+
+    fn to_rgb(&self, raw: &[u8]) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        match self.pixel_format {
+            PIX_FMT_YUYV => Ok(yuyv_to_rgb(raw, self.width, self.height, self.stride)),
+            PIX_FMT_RGB24 => {
+                let row = self.width * 3;
+                let stride = self.stride.max(row);
+                Ok(raw.chunks(stride).take(self.height).flat_map(|line| &line[..row.min(line.len())]).copied().collect())
+            }
+            _ => Ok(image::load_from_memory(raw)?.to_rgb8().into_raw()),
+        }
+    }
+}
+
+impl FrameSource for V4l2Camera {
+    fn next_frame(&mut self) -> Result<Frame, Box<dyn std::error::Error>> {
+        let mut poll_fd = libc::pollfd { fd: self.fd.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, FRAME_TIMEOUT_MS) };
+        if ready < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        if ready == 0 {
+            return Err("timed out waiting for a camera frame".into());
+        }
+
+        let mut buffer = self.empty_buffer(0);
+        xioctl(&self.fd, VIDIOC_DQBUF, &mut buffer)?;
+
+        let mapped = &self.buffers[buffer.index as usize];
+        let used = (buffer.bytesused as usize).min(mapped.length);
+        let raw = unsafe { std::slice::from_raw_parts(mapped.ptr as *const u8, used) };
+        let data = self.to_rgb(raw);
+
+        // Hand the buffer back before reporting any conversion error
+        xioctl(&self.fd, VIDIOC_QBUF, &mut buffer)?;
+
+        let data = data?;
+        if data.len() < self.width * self.height * 3 {
+            return Err("short frame from camera".into());
+        }
+        Ok(Frame { data, width: self.width, height: self.height })
+    }
+}
+
+impl Drop for V4l2Camera {
+    fn drop(&mut self) {
+        let mut buf_type = BUF_TYPE_VIDEO_CAPTURE as libc::c_int;
+        let _ = xioctl(&self.fd, VIDIOC_STREAMOFF, &mut buf_type);
+        for mapped in &self.buffers {
+            unsafe { libc::munmap(mapped.ptr, mapped.length) };
+        }
+    }
+}
+
+/// Packed YUYV 4:2:2 to RGB24 (BT.601, studio range)
+fn yuyv_to_rgb(raw: &[u8], width: usize, height: usize, stride: usize) -> Vec<u8> {
+    let stride = stride.max(width * 2);
+    let mut rgb = Vec::with_capacity(width * height * 3);
+
+    for y in 0..height {
+        let Some(line) = raw.get(y * stride..y * stride + width * 2) else { break };
+
+        for pair in line.chunks_exact(4) {
+            rgb.extend_from_slice(&ycbcr_to_rgb(pair[0], pair[1], pair[3]));
+            rgb.extend_from_slice(&ycbcr_to_rgb(pair[2], pair[1], pair[3]));
+        }
+    }
+
+    rgb
+}
@@ -0,0 +1,298 @@
+// Video decoding for VideoEncoder and /video
+// Animated GIF, APNG and Y4M are decoded in pure Rust; other containers go through
+// the ffmpeg binary when built with the `ffmpeg` feature.
+
+use image::{AnimationDecoder, DynamicImage, RgbImage};
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+pub type VideoError = Box<dyn std::error::Error>;
+
+/// Decoded frames, in playback order, produced lazily
+pub type VideoFrames = Box<dyn Iterator<Item = Result<DynamicImage, VideoError>>>;
+
+/// Largest frame accepted from a file header, in pixels (a 16K UHD frame)
+const MAX_FRAME_PIXELS: usize = 15360 * 8640;
+
+/// Reject header sizes that would overflow or exhaust memory before the planes are read
+fn check_frame_size(width: usize, height: usize) -> Result<(), VideoError> {
+    match width.checked_mul(height) {
+        Some(pixels) if pixels <= MAX_FRAME_PIXELS => Ok(()),
+        _ => Err(format!("video frame size {}x{} is too large", width, height).into()),
+    }
+}
+
+/// Open a video file and decode its frames one by one.
+/// The container is detected from the file's magic bytes, not its extension.
+pub fn decode_frames<P: AsRef<Path>>(path: P) -> Result<VideoFrames, VideoError> {
+    let path = path.as_ref();
+    let mut reader = BufReader::new(File::open(path)?);
+    let magic = reader.fill_buf()?.get(..9).map(|m| m.to_vec()).unwrap_or_default();
+
+    if magic.starts_with(b"GIF8") {
+        let frames = GifDecoder::new(reader)?.into_frames();
+        return Ok(Box::new(frames.map(|f| Ok(DynamicImage::ImageRgba8(f?.into_buffer())))));
+    }
+
+    if magic.starts_with(b"\x89PNG") {
+        let decoder = PngDecoder::new(reader)?;
+        if decoder.is_apng()? {
+            let frames = decoder.apng()?.into_frames();
+            return Ok(Box::new(frames.map(|f| Ok(DynamicImage::ImageRgba8(f?.into_buffer())))));
+        }
+        // A still PNG is a one-frame video
+        let still = DynamicImage::from_decoder(decoder)?;
+        return Ok(Box::new(std::iter::once(Ok(still))));
+    }
+
+    if magic.starts_with(b"YUV4MPEG2") {
+        return Ok(Box::new(Y4mFrames::new(reader)?));
+    }
+
+    ffmpeg_frames(path)
+}
+
+#[cfg(feature = "ffmpeg")]
+fn ffmpeg_frames(path: &Path) -> Result<VideoFrames, VideoError> {
+    use std::process::{Command, Stdio};
+
+    // PPM frames are self-describing, so no separate probe for the size is needed
+    let mut child = Command::new("ffmpeg")
+        .args(["-v", "error", "-i"])
+        .arg(path)
+        .args(["-f", "image2pipe", "-c:v", "ppm", "-"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run ffmpeg: {}", e))?;
+
+    let stdout = child.stdout.take().ok_or("ffmpeg has no stdout")?;
+    Ok(Box::new(PpmStream { reader: BufReader::new(stdout), _child: ChildGuard(child) }))
+}
+
+#[cfg(not(feature = "ffmpeg"))]
+fn ffmpeg_frames(path: &Path) -> Result<VideoFrames, VideoError> {
+    Err(format!(
+        "{}: unsupported video container (GIF, APNG and Y4M are built in; rebuild with --features ffmpeg for others)",
+        path.display()
+    ).into())
+}
+
+/// YUV4MPEG2 stream: a text header, then "FRAME" lines each followed by raw planes
+struct Y4mFrames<R: BufRead> {
+    reader: R,
+    width: usize,
+    height: usize,
+    chroma: Option<(usize, usize)>,   // Subsampling (x, y) of the Cb/Cr planes, None = greyscale
+    done: bool,
+}
+
+impl<R: BufRead> Y4mFrames<R> {
+    fn new(mut reader: R) -> Result<Self, VideoError> {
+        let header = read_line(&mut reader)?.ok_or("empty Y4M file")?;
+        let mut width = 0;
+        let mut height = 0;
+        let mut chroma = Some((2, 2)); // C420jpeg is the default
+
+        for token in header.split(' ').skip(1) {
+            let Some(tag) = token.chars().next() else { continue };
+            let value = &token[tag.len_utf8()..];
+            match tag {
+                'W' => width = value.parse()?,
+                'H' => height = value.parse()?,
+                'C' => chroma = match value {
+                    "420" | "420jpeg" | "420paldv" | "420mpeg2" => Some((2, 2)),
+                    "422" => Some((2, 1)),
+                    "444" => Some((1, 1)),
+                    "mono" => None,
+                    other => return Err(format!("unsupported Y4M colorspace C{}", other).into()),
+                },
+                _ => {} // Frame rate, interlacing, aspect - irrelevant here
+            }
+        }
+
+        if width == 0 || height == 0 {
+            return Err("Y4M header has no frame size".into());
+        }
+        check_frame_size(width, height)?;
+
+        Ok(Y4mFrames { reader, width, height, chroma, done: false })
+    }
+
+    fn read_frame(&mut self) -> Result<Option<DynamicImage>, VideoError> {
+        let Some(line) = read_line(&mut self.reader)? else { return Ok(None) };
+        if !line.starts_with("FRAME") {
+            return Err("corrupt Y4M stream: expected FRAME".into());
+        }
+
+        let (w, h) = (self.width, self.height);
+        let mut luma = vec![0u8; w * h];
+        self.reader.read_exact(&mut luma)?;
+
+        let mut rgb = RgbImage::new(w as u32, h as u32);
+        match self.chroma {
+            Some((sx, sy)) => {
+                let (cw, ch) = (w.div_ceil(sx), h.div_ceil(sy));
+                let mut cb = vec![0u8; cw * ch];
+                let mut cr = vec![0u8; cw * ch];
+                self.reader.read_exact(&mut cb)?;
+                self.reader.read_exact(&mut cr)?;
+
+                for (i, pixel) in rgb.pixels_mut().enumerate() {
+                    let (x, y) = (i % w, i / w);
+                    let c = (y / sy) * cw + x / sx;
+                    pixel.0 = ycbcr_to_rgb(luma[i], cb[c], cr[c]);
+                }
+            }
+            None => {
+                for (pixel, &y) in rgb.pixels_mut().zip(&luma) {
+                    pixel.0 = ycbcr_to_rgb(y, 128, 128);
+                }
+            }
+        }
+
+        Ok(Some(DynamicImage::ImageRgb8(rgb)))
+    }
+}
+
+impl<R: BufRead> Iterator for Y4mFrames<R> {
+    type Item = Result<DynamicImage, VideoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let frame = self.read_frame().transpose();
+        if !matches!(frame, Some(Ok(_))) {
+            self.done = true; // Stop after the end or the first error
+        }
+        frame
+    }
+}
+
+/// One '\n'-terminated header line, None at end of stream
+fn read_line<R: BufRead>(reader: &mut R) -> Result<Option<String>, VideoError> {
+    let mut line = Vec::new();
+    if reader.read_until(b'\n', &mut line)? == 0 {
+        return Ok(None);
+    }
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    }
+    Ok(Some(String::from_utf8(line)?))
+}
+
+/// YCbCr (BT.601, studio range) to RGB
+pub(crate) fn ycbcr_to_rgb(y: u8, cb: u8, cr: u8) -> [u8; 3] {
+    let c = 298 * (y as i32 - 16);
+    let d = cb as i32 - 128;
+    let e = cr as i32 - 128;
+    [
+        ((c + 409 * e + 128) >> 8).clamp(0, 255) as u8,
+        ((c - 100 * d - 208 * e + 128) >> 8).clamp(0, 255) as u8,
+        ((c + 516 * d + 128) >> 8).clamp(0, 255) as u8,
+    ]
+}
+
+// Kills ffmpeg if the frames are dropped before it finishes
+#[cfg(feature = "ffmpeg")]
+struct ChildGuard(std::process::Child);
+
+#[cfg(feature = "ffmpeg")]
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Back-to-back binary PPM (P6) images, as written by ffmpeg's image2pipe
+#[cfg(feature = "ffmpeg")]
+struct PpmStream<R: BufRead> {
+    reader: R,
+    _child: ChildGuard,
+}
+
+#[cfg(feature = "ffmpeg")]
+impl<R: BufRead> PpmStream<R> {
+    fn token(&mut self) -> Result<Option<String>, VideoError> {
+        let mut token = String::new();
+        loop {
+            let mut byte = [0u8; 1];
+            if self.reader.read(&mut byte)? == 0 {
+                return Ok(if token.is_empty() { None } else { Some(token) });
+            }
+            match byte[0] {
+                b'#' => { self.reader.read_until(b'\n', &mut Vec::new())?; }
+                b if b.is_ascii_whitespace() => if !token.is_empty() { return Ok(Some(token)) },
+                b => token.push(b as char),
+            }
+        }
+    }
+
+    fn read_frame(&mut self) -> Result<Option<DynamicImage>, VideoError> {
+        let Some(magic) = self.token()? else { return Ok(None) };
+        if magic != "P6" {
+            return Err("unexpected ffmpeg output".into());
+        }
+        let mut field = || -> Result<u32, VideoError> { Ok(self.token()?.ok_or("truncated PPM header")?.parse()?) };
+        let (width, height, max) = (field()?, field()?, field()?);
+        if max != 255 {
+            return Err("16-bit PPM frames are not supported".into());
+        }
+        check_frame_size(width as usize, height as usize)?;
+
+        let mut data = vec![0u8; width as usize * height as usize * 3];
+        self.reader.read_exact(&mut data)?;
+        let img = RgbImage::from_raw(width, height, data).ok_or("bad PPM frame")?;
+        Ok(Some(DynamicImage::ImageRgb8(img)))
+    }
+}
+
+#[cfg(feature = "ffmpeg")]
+impl<R: BufRead> Iterator for PpmStream<R> {
+    type Item = Result<DynamicImage, VideoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_frame().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn y4m_header_tolerates_multibyte_tokens() {
+        // 2x2 C444: one FRAME of luma 235 (white), neutral chroma
+        let mut stream = "YUV4MPEG2 W2 H2 F25:1 Xcomment=é éclair C444\nFRAME\n".as_bytes().to_vec();
+        stream.extend([235; 4]);
+        stream.extend([128; 8]);
+
+        let frames: Vec<DynamicImage> = Y4mFrames::new(Cursor::new(stream)).unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!((frames[0].width(), frames[0].height()), (2, 2));
+        assert!(frames[0].to_rgb8().pixels().all(|p| p.0 == [255, 255, 255]));
+    }
+
+    #[test]
+    fn y4m_header_without_size_is_an_error() {
+        assert!(Y4mFrames::new(Cursor::new(b"YUV4MPEG2 \xc3\xa9 C420\n".to_vec())).is_err());
+    }
+
+    #[test]
+    fn y4m_header_with_huge_size_is_an_error() {
+        // Would allocate 10 GB per luma plane if accepted
+        let stream = b"YUV4MPEG2 W100000 H100000 Cmono\nFRAME\n".to_vec();
+        let err = Y4mFrames::new(Cursor::new(stream)).err().expect("oversized header accepted");
+        assert!(err.to_string().contains("too large"));
+
+        let overflow = format!("YUV4MPEG2 W{} H2 Cmono\n", usize::MAX);
+        assert!(Y4mFrames::new(Cursor::new(overflow.into_bytes())).is_err());
+    }
+}